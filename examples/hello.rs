@@ -4,7 +4,7 @@ use olma::core::AnyView;
 use olma::piet::Color;
 use olma::widgets::lazy::LazyData;
 use olma::widgets::*;
-use olma::{Application, ViewExt};
+use olma::{Application, Command, ViewExt};
 
 struct App {
     num: i32,
@@ -19,7 +19,7 @@ enum Msg {
 impl Application for App {
     type Msg = Msg;
 
-    fn update(&mut self, msg: Self::Msg) {
+    fn update(&mut self, msg: Self::Msg) -> Command<Msg> {
         match msg {
             Msg::Add => {
                 self.num += 1;
@@ -29,6 +29,7 @@ impl Application for App {
                 self.list.pop();
             }
         }
+        Command::none()
     }
 
     fn view<'a>(&'a self) -> AnyView<'a> {
@@ -52,5 +53,6 @@ fn main() {
     olma::launch(App {
         num: 3,
         list: LazyData::new(vec![1, 2, 3]),
-    });
+    })
+    .unwrap();
 }
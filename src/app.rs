@@ -1,16 +1,83 @@
 use std::any::Any;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem;
+use std::thread;
 
-use crate::{core::AnyView, view_bump::ViewBump, widget_host::WidgetHost};
+use std::rc::Rc;
+
+use druid_shell::{IdleToken, TimerToken};
+
+use crate::backend::WindowHost;
+use crate::command::{self, Action, Command};
+use crate::ext_event::{ExtEventHost, ExtEventSink, EXT_EVENT_IDLE_TOKEN};
+use crate::launch::WindowConfig;
+use crate::widgets::keyed::Key;
+use crate::window::{Window, WindowId};
+use crate::{core::AnyView, view_bump::ViewBump};
+
+/// How many times messages submitted while updating are fed back to the app,
+/// before giving up until the next event.
+const MAX_MESSAGE_ROUNDS: usize = 100;
 
 pub trait AppDyn {
-    fn update(&mut self, msg: Box<dyn Any>);
+    fn init(&mut self, sink: ExtEventSink<Box<dyn Any>>);
+    fn update(&mut self, msg: Box<dyn Any>) -> Command<Box<dyn Any>>;
     fn view<'a>(&'a self) -> AnyView<'a>;
+    fn title(&self) -> Option<String>;
+    fn windows<'a>(&'a self) -> Vec<WindowView<'a>>;
+    fn close_requested(&self, window: WindowId) -> Option<Box<dyn Any>>;
+    fn as_any(&self) -> &dyn Any;
 }
 
 pub trait Application: 'static {
     type Msg;
-    fn update(&mut self, msg: Self::Msg);
+    /// Called once before the window opens, with a sink for messages from other threads.
+    fn init(&mut self, _sink: ExtEventSink<Self::Msg>) {}
+    fn update(&mut self, msg: Self::Msg) -> Command<Self::Msg>;
     fn view<'a>(&'a self) -> AnyView<'a>;
+    /// The window title, overriding the one from [`WindowConfig`].
+    ///
+    /// [`WindowConfig`]: crate::WindowConfig
+    fn title(&self) -> Option<String> {
+        None
+    }
+    /// Windows to show besides the main one.
+    ///
+    /// A window is opened when its key first shows up, and closed once it is
+    /// gone.
+    fn windows<'a>(&'a self) -> Vec<WindowView<'a>> {
+        Vec::new()
+    }
+    /// Called when the user asks to close `window`.
+    ///
+    /// Returning `None` closes the window right away. A message is delivered to
+    /// [`update`](Application::update) instead, which can confirm the close with
+    /// [`Command::close_window`] or by dropping the window from
+    /// [`windows`](Application::windows), or ignore it to keep the window open.
+    ///
+    /// A keyed window closed by the user stays closed for as long as its key
+    /// stays in [`windows`](Application::windows).
+    fn close_requested(&self, _window: WindowId) -> Option<Self::Msg> {
+        None
+    }
+}
+
+/// A window returned from [`Application::windows`].
+pub struct WindowView<'a> {
+    pub(crate) key: Key,
+    pub(crate) config: WindowConfig,
+    pub(crate) view: AnyView<'a>,
+}
+
+impl<'a> WindowView<'a> {
+    pub fn new(key: impl Hash, config: WindowConfig, view: AnyView<'a>) -> Self {
+        WindowView {
+            key: Key::new(key),
+            config,
+            view,
+        }
+    }
 }
 
 impl<Msg, A> AppDyn for A
@@ -18,42 +85,267 @@ where
     A: Application<Msg = Msg>,
     Msg: 'static,
 {
-    fn update(&mut self, msg: Box<dyn Any>) {
+    fn init(&mut self, sink: ExtEventSink<Box<dyn Any>>) {
+        Application::init(self, sink.cast());
+    }
+
+    fn update(&mut self, msg: Box<dyn Any>) -> Command<Box<dyn Any>> {
         match msg.downcast::<Msg>() {
-            Ok(msg) => self.update(*msg),
-            Err(msg) => eprintln!("Unknown Message: {:?}", msg),
+            Ok(msg) => self.update(*msg).erase(),
+            Err(msg) => {
+                eprintln!("Unknown Message: {:?}", msg);
+                Command::none()
+            }
         }
     }
 
     fn view<'a>(&'a self) -> AnyView<'a> {
         self.view()
     }
+
+    fn title(&self) -> Option<String> {
+        Application::title(self)
+    }
+
+    fn windows<'a>(&'a self) -> Vec<WindowView<'a>> {
+        Application::windows(self)
+    }
+
+    fn close_requested(&self, window: WindowId) -> Option<Box<dyn Any>> {
+        Application::close_requested(self, window).map(|msg| Box::new(msg) as Box<dyn Any>)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
+/// The application and all of its windows.
 pub struct AppHolder {
     app: Box<dyn AppDyn>,
-    host: WidgetHost,
+    windows: Vec<Window>,
+    /// Windows added by the app that still have to be opened.
+    pending_windows: Vec<(WindowId, WindowConfig)>,
+    /// Windows to close once the app is no longer borrowed.
+    closing_windows: Vec<(WindowId, Rc<dyn WindowHost>)>,
+    /// Keyed windows closed by the user, that the app still lists.
+    closed_windows: Vec<WindowId>,
+    /// Messages that don't come from any window.
+    messages: Vec<Box<dyn Any>>,
+    /// Messages waiting on a timer started by [`Command::after`].
+    timers: HashMap<TimerToken, Box<dyn Any>>,
+    ext_events: ExtEventHost,
 }
 
 impl AppHolder {
-    pub fn new(app: Box<dyn AppDyn>) -> Self {
+    pub fn new(mut app: Box<dyn AppDyn>, config: &WindowConfig) -> Self {
+        let ext_events = ExtEventHost::default();
+        app.init(ext_events.sink());
         ViewBump::init();
         let widget = app.view().build();
         ViewBump::reset();
-        let host = WidgetHost::new(widget);
+        let title = app.title().unwrap_or_else(|| config.title.clone());
+        let main = Window::new(WindowId::Main, widget, title);
+
+        let mut this = Self {
+            app,
+            windows: vec![main],
+            pending_windows: Vec::new(),
+            closing_windows: Vec::new(),
+            closed_windows: Vec::new(),
+            messages: Vec::new(),
+            timers: HashMap::new(),
+            ext_events,
+        };
+        ViewBump::init();
+        this.update_windows();
+        ViewBump::reset();
+        this
+    }
 
-        Self { app, host }
+    pub(crate) fn app(&self) -> &dyn AppDyn {
+        &*self.app
     }
 
-    pub fn with_host<R>(&mut self, f: impl FnOnce(&mut WidgetHost) -> R) -> R {
-        f(&mut self.host)
+    /// The open windows.
+    pub(crate) fn window_ids(&self) -> Vec<WindowId> {
+        self.windows.iter().map(|w| w.id).collect()
     }
 
-    pub fn update(&mut self, msg: Box<dyn Any>) {
-        self.app.update(msg);
+    pub(crate) fn window_mut(&mut self, id: WindowId) -> Option<&mut Window> {
+        self.windows.iter_mut().find(|w| w.id == id)
+    }
+
+    /// Feed all outstanding messages to the app, until there are none left.
+    ///
+    /// Updating can submit more messages, so a widget that submits one on
+    /// every update would keep this going forever. After `MAX_MESSAGE_ROUNDS`
+    /// rounds the rest is left for the next event.
+    pub fn run_messages(&mut self) {
+        for _ in 0..MAX_MESSAGE_ROUNDS {
+            let mut messages = mem::take(&mut self.messages);
+            for window in &mut self.windows {
+                messages.append(&mut window.messages);
+            }
+            if messages.is_empty() {
+                return;
+            }
+            for msg in messages {
+                self.update(msg);
+            }
+        }
+        eprintln!(
+            "Messages still submitted after {} rounds of updates, is a widget submitting one on every update?",
+            MAX_MESSAGE_ROUNDS
+        );
+    }
+
+    fn update(&mut self, msg: Box<dyn Any>) {
+        let command = self.app.update(msg);
+        self.run_command(command);
         ViewBump::init();
-        let next_view = self.app.view();
-        self.host.update(next_view);
+        let app = &*self.app;
+        if let Some(main) = self.windows.iter_mut().find(|w| w.id == WindowId::Main) {
+            main.update(app.view(), app.title());
+        }
+        self.update_windows();
         ViewBump::reset();
     }
+
+    /// Bring the windows from [`Application::windows`] up to date.
+    fn update_windows(&mut self) {
+        let views = self.app.windows();
+        let mut keys = Vec::with_capacity(views.len());
+        for WindowView { key, config, view } in views {
+            let id = WindowId::Keyed(key);
+            keys.push(id);
+            if self.closed_windows.contains(&id) {
+                continue;
+            }
+            match self.windows.iter_mut().find(|w| w.id == id) {
+                Some(window) => window.update(view, Some(config.title)),
+                None => {
+                    let window = Window::new(id, view.build(), config.title.clone());
+                    self.windows.push(window);
+                    self.pending_windows.push((id, config));
+                }
+            }
+        }
+
+        let (keep, closed) = mem::take(&mut self.windows)
+            .into_iter()
+            .partition(|w| w.id == WindowId::Main || keys.contains(&w.id));
+        self.windows = keep;
+        self.pending_windows.retain(|(id, _)| keys.contains(id));
+        self.closed_windows.retain(|id| keys.contains(id));
+        for mut window in closed {
+            window.window_disconnected();
+            self.messages.append(&mut window.messages);
+            self.closing_windows.push((window.id, window.handle.clone()));
+        }
+    }
+
+    /// Windows that were added since the last call, and have to be opened.
+    pub fn take_pending_windows(&mut self) -> Vec<(WindowId, WindowConfig)> {
+        mem::take(&mut self.pending_windows)
+    }
+
+    /// Windows that have to be closed.
+    ///
+    /// Closing a window may destroy it right away, so this has to happen
+    /// outside of any borrow of the app.
+    pub fn take_closing_windows(&mut self) -> Vec<(WindowId, Rc<dyn WindowHost>)> {
+        mem::take(&mut self.closing_windows)
+    }
+
+    pub fn connect(&mut self, id: WindowId, handle: Rc<dyn WindowHost>) {
+        if let Some(window) = self.window_mut(id) {
+            window.window_connected(handle);
+        }
+        if id == WindowId::Main {
+            self.connect_ext_events();
+        }
+    }
+
+    /// Wake up the run loop through a window that is still around.
+    fn connect_ext_events(&mut self) {
+        // without a waker, messages wait until someone drains the queue
+        if let Some(waker) = self.windows.iter().find_map(|w| w.handle.waker()) {
+            self.ext_events.connect(waker);
+        }
+    }
+
+    /// The user asked to close the window.
+    pub fn request_close(&mut self, id: WindowId) {
+        match self.app.close_requested(id) {
+            Some(msg) => self.messages.push(msg),
+            None => self.close_window(id),
+        }
+    }
+
+    /// The platform window is gone.
+    pub fn disconnect(&mut self, id: WindowId) {
+        if let Some(index) = self.windows.iter().position(|w| w.id == id) {
+            let mut window = self.windows.remove(index);
+            window.window_disconnected();
+            // the unmount messages outlive the window
+            self.messages.append(&mut window.messages);
+            if !self.windows.is_empty() {
+                self.connect_ext_events();
+            }
+        }
+    }
+
+    pub fn has_windows(&self) -> bool {
+        !self.windows.is_empty()
+    }
+
+    pub fn timer(&mut self, id: WindowId, token: TimerToken) {
+        match self.timers.remove(&token) {
+            Some(msg) => self.messages.push(msg),
+            None => {
+                if let Some(window) = self.window_mut(id) {
+                    window.timer(token);
+                }
+            }
+        }
+    }
+
+    pub fn idle(&mut self, token: IdleToken) {
+        if token == EXT_EVENT_IDLE_TOKEN {
+            let messages = self.ext_events.drain();
+            self.messages.extend(messages);
+        }
+    }
+
+    fn close_window(&mut self, id: WindowId) {
+        if let WindowId::Keyed(_) = id {
+            self.closed_windows.push(id);
+        }
+        if let Some(window) = self.window_mut(id) {
+            let handle = window.handle.clone();
+            self.closing_windows.push((id, handle));
+        }
+    }
+
+    fn run_command(&mut self, command: Command<Box<dyn Any>>) {
+        // timers can go through any window, they are routed back here
+        let any = self.windows.first().map(|w| w.handle.clone());
+        for action in command.actions {
+            match action {
+                Action::After(duration, msg) => match &any {
+                    Some(window) => {
+                        let token = window.request_timer(duration);
+                        self.timers.insert(token, msg);
+                    }
+                    None => eprintln!("No open window, dropping timer"),
+                },
+                Action::Perform(future) => {
+                    let sink = self.ext_events.sink::<()>();
+                    thread::spawn(move || sink.submit_any(command::block_on(future)));
+                }
+                Action::CloseWindow(id) => self.close_window(id),
+            }
+        }
+    }
 }
@@ -0,0 +1,117 @@
+use std::cell::{Cell, RefCell};
+use std::time::Duration;
+
+use crate::ext_event::Waker;
+use crate::kurbo::Rect;
+use crate::piet::{Device, Error, PietText, RenderContext};
+use druid_shell::{Cursor, TimerToken};
+
+/// What widgets need from the platform window they live in.
+///
+/// druid-shell's `WindowHandle` is one implementation, [`HeadlessWindow`]
+/// another one.
+pub trait WindowHost {
+    fn invalidate(&self);
+    fn invalidate_rect(&self, rect: Rect);
+    /// The factory for text layouts.
+    fn text(&self) -> PietText;
+    fn request_timer(&self, deadline: Duration) -> TimerToken;
+    fn set_cursor(&self, cursor: &Cursor);
+    fn set_title(&self, title: &str);
+    fn close(&self);
+    /// Something that wakes up the run loop from other threads, so that
+    /// [`ExtEventSink`] messages get delivered.
+    ///
+    /// [`ExtEventSink`]: crate::ExtEventSink
+    fn waker(&self) -> Option<Waker>;
+}
+
+/// Stands in for the platform window of a [`Window`](crate::Window) until it
+/// is connected.
+pub(crate) struct DetachedWindow;
+
+impl WindowHost for DetachedWindow {
+    fn invalidate(&self) {}
+
+    fn invalidate_rect(&self, _rect: Rect) {}
+
+    fn text(&self) -> PietText {
+        HeadlessWindow::new()
+            .expect("failed to create a text factory")
+            .text()
+    }
+
+    fn request_timer(&self, _deadline: Duration) -> TimerToken {
+        TimerToken::INVALID
+    }
+
+    fn set_cursor(&self, _cursor: &Cursor) {}
+
+    fn set_title(&self, _title: &str) {}
+
+    fn close(&self) {}
+
+    fn waker(&self) -> Option<Waker> {
+        None
+    }
+}
+
+/// A window without a display, for tests and offscreen rendering.
+pub struct HeadlessWindow {
+    text: PietText,
+    invalidated: Cell<bool>,
+    timers: RefCell<Vec<TimerToken>>,
+}
+
+impl HeadlessWindow {
+    pub fn new() -> Result<Self, Error> {
+        let mut device = Device::new()?;
+        let mut target = device.bitmap_target(1, 1, 1.0)?;
+        let text = target.render_context().text().clone();
+        Ok(HeadlessWindow {
+            text,
+            invalidated: Cell::new(false),
+            timers: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Whether a repaint was requested since the last call.
+    pub fn take_invalidated(&self) -> bool {
+        self.invalidated.replace(false)
+    }
+
+    /// The timers requested since the last call, oldest first.
+    pub fn take_timers(&self) -> Vec<TimerToken> {
+        self.timers.take()
+    }
+}
+
+impl WindowHost for HeadlessWindow {
+    fn invalidate(&self) {
+        self.invalidated.set(true);
+    }
+
+    fn invalidate_rect(&self, _rect: Rect) {
+        self.invalidated.set(true);
+    }
+
+    fn text(&self) -> PietText {
+        self.text.clone()
+    }
+
+    fn request_timer(&self, _deadline: Duration) -> TimerToken {
+        let token = TimerToken::next();
+        self.timers.borrow_mut().push(token);
+        token
+    }
+
+    fn set_cursor(&self, _cursor: &Cursor) {}
+
+    fn set_title(&self, _title: &str) {}
+
+    fn close(&self) {}
+
+    fn waker(&self) -> Option<Waker> {
+        None
+    }
+}
@@ -34,7 +34,7 @@ use crate::kurbo::Size;
 /// [`layout`]: trait.Widget.html#tymethod.layout
 /// [Flutter BoxConstraints]: https://api.flutter.dev/flutter/rendering/BoxConstraints-class.html
 /// [rounded away from zero]: struct.Size.html#method.expand
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct BoxConstraints {
     min: Size,
     max: Size,
@@ -120,6 +120,18 @@ impl BoxConstraints {
         self.max.height.is_finite()
     }
 
+    /// Whether these constraints can be satisfied by a finite size.
+    pub fn is_valid(&self) -> bool {
+        0.0 <= self.min.width
+            && self.min.width <= self.max.width
+            && 0.0 <= self.min.height
+            && self.min.height <= self.max.height
+            && self.min.expand() == self.min
+            && self.max.expand() == self.max
+            && self.min.width.is_finite()
+            && self.min.height.is_finite()
+    }
+
     /// Check to see if these constraints are legit.
     ///
     /// Logs a warning if BoxConstraints are invalid.
@@ -0,0 +1,94 @@
+use std::any::Any;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+use std::time::Duration;
+
+use crate::window::WindowId;
+
+type BoxFuture = Pin<Box<dyn Future<Output = Box<dyn Any + Send>> + Send>>;
+
+/// Side effects requested by [`Application::update`].
+///
+/// Whatever message a command produces is fed back into `update`.
+///
+/// [`Application::update`]: crate::Application::update
+pub struct Command<Msg> {
+    pub(crate) actions: Vec<Action>,
+    _msg: PhantomData<fn() -> Msg>,
+}
+
+pub(crate) enum Action {
+    After(Duration, Box<dyn Any>),
+    Perform(BoxFuture),
+    CloseWindow(WindowId),
+}
+
+impl<Msg: 'static> Command<Msg> {
+    fn from_actions(actions: Vec<Action>) -> Self {
+        Command {
+            actions,
+            _msg: PhantomData,
+        }
+    }
+
+    /// Do nothing.
+    pub fn none() -> Self {
+        Self::from_actions(Vec::new())
+    }
+
+    /// Run all of `commands`.
+    pub fn batch(commands: impl IntoIterator<Item = Command<Msg>>) -> Self {
+        Self::from_actions(commands.into_iter().flat_map(|c| c.actions).collect())
+    }
+
+    /// Run `future` on a background thread, and deliver its output as a message.
+    pub fn perform<T>(
+        future: impl Future<Output = T> + Send + 'static,
+        f: impl FnOnce(T) -> Msg + Send + 'static,
+    ) -> Self
+    where
+        Msg: Send,
+    {
+        let future = async move { Box::new(f(future.await)) as Box<dyn Any + Send> };
+        Self::from_actions(vec![Action::Perform(Box::pin(future))])
+    }
+
+    /// Deliver `msg` once `duration` has passed.
+    pub fn after(duration: Duration, msg: Msg) -> Self {
+        Self::from_actions(vec![Action::After(duration, Box::new(msg))])
+    }
+
+    /// Close the window `id`.
+    pub fn close_window(id: WindowId) -> Self {
+        Self::from_actions(vec![Action::CloseWindow(id)])
+    }
+
+    pub(crate) fn erase(self) -> Command<Box<dyn Any>> {
+        Command::from_actions(self.actions)
+    }
+}
+
+/// Drive `future` to completion on the current thread.
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
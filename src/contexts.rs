@@ -1,14 +1,17 @@
 use std::any::Any;
+use std::time::Duration;
 
 use crate::kurbo::Rect;
+use crate::shell::{Cursor, Region, Scale, TimerToken};
 use crate::piet::{Piet, PietText, RenderContext};
-use druid_shell::WindowHandle;
 
-use crate::widget_host::WidgetState;
+use crate::backend::WindowHost;
+use crate::debug_paint::DebugPaint;
+use crate::widget_host::{FocusChange, WidgetState};
 use crate::widgets::layout::LayoutState;
 
 pub struct EventCtx<'a> {
-    pub(crate) window: &'a WindowHandle,
+    pub(crate) window: &'a dyn WindowHost,
     pub(crate) state: &'a mut WidgetState,
     pub(crate) layout_state: &'a LayoutState,
     pub(crate) messages: &'a mut Vec<Box<dyn Any>>,
@@ -18,10 +21,13 @@ pub struct PaintCtx<'a, 'b> {
     pub(crate) state: &'a WidgetState,
     pub(crate) layout_state: &'a LayoutState,
     pub(crate) render_ctx: &'a mut Piet<'b>,
+    pub(crate) debug_paint: Option<DebugPaint>,
+    /// The damaged region, in window coordinates.
+    pub(crate) region: &'a Region,
 }
 
 pub struct LayoutCtx<'a> {
-    pub(crate) window: &'a WindowHandle,
+    pub(crate) window: &'a dyn WindowHost,
     pub(crate) state: &'a WidgetState,
     pub(crate) layout_state: &'a LayoutState,
 }
@@ -47,14 +53,68 @@ impl<'a> EventCtx<'a> {
         self.state.keyboard_focus
     }
 
+    /// Put the widget in the Tab focus chain. Call this from `mount`.
+    pub fn register_for_focus(&mut self) {
+        self.state.focusable = true;
+    }
+
+    /// Ask for keyboard focus. The widget is told through `focus_changed`.
+    pub fn request_focus(&mut self) {
+        self.state.request_focus = Some(FocusChange::Focus(self.state.id));
+    }
+
+    /// Give up keyboard focus, if the widget has it.
+    pub fn resign_focus(&mut self) {
+        if self.state.keyboard_focus {
+            self.state.request_focus = Some(FocusChange::Resign);
+        }
+    }
+
     pub fn request_paint(&mut self) {
+        self.request_paint_rect(self.layout_state.size.to_rect());
+    }
+
+    /// Repaint part of the widget, `rect` is in the widget's coordinates.
+    pub fn request_paint_rect(&mut self, rect: Rect) {
+        let origin = self.layout_state.window_origin.to_vec2();
+        self.state.needs_paint = true;
+        self.window.invalidate_rect(rect + origin);
+    }
+
+    /// Lay out the widget again before the next paint.
+    pub fn request_layout(&mut self) {
+        self.state.needs_layout = true;
+        self.state.needs_paint = true;
         self.window.invalidate();
     }
 
+    /// Mark the event as handled, so it isn't passed on to other widgets.
+    pub fn set_handled(&mut self) {
+        self.state.is_handled = true;
+    }
+
+    /// Whether a widget already handled the event.
+    pub fn is_handled(&self) -> bool {
+        self.state.is_handled
+    }
+
+    /// Get a [`UiWidget::timer`](crate::UiWidget::timer) call with the
+    /// returned token after `deadline`. Only this widget receives it.
+    pub fn request_timer(&mut self, deadline: Duration) -> TimerToken {
+        let token = self.window.request_timer(deadline);
+        self.state.timers.insert(token);
+        token
+    }
+
     pub fn request_update(&mut self) {
         self.state.request_update = true;
     }
 
+    /// Set the mouse cursor of the window.
+    pub fn set_cursor(&mut self, cursor: &Cursor) {
+        self.window.set_cursor(cursor);
+    }
+
     pub fn submit_message(&mut self, msg: Box<dyn Any>)
     {
 
@@ -66,6 +126,11 @@ impl LayoutCtx<'_> {
     pub fn text(&self) -> PietText {
         self.window.text()
     }
+
+    /// The ratio of device pixels to display points.
+    pub fn scale(&self) -> Scale {
+        self.layout_state.scale
+    }
 }
 
 impl<'c> std::ops::Deref for PaintCtx<'_, 'c> {
@@ -95,6 +160,11 @@ impl PaintCtx<'_, '_> {
         self.state.keyboard_focus
     }
 
+    /// The ratio of device pixels to display points.
+    pub fn scale(&self) -> Scale {
+        self.layout_state.scale
+    }
+
     pub fn frame(&self) -> Rect {
         self.layout_state.size.to_rect()
     }
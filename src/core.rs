@@ -3,18 +3,20 @@ use std::any::{Any, TypeId};
 use druid_shell::kurbo::Size;
 use druid_shell::{KeyEvent, TimerToken};
 
+use crate::inspect::InspectNode;
+use crate::widget_host::WidgetHost;
 use crate::{vbox_dyn, BoxConstraints, EventCtx, LayoutCtx, MouseEvent, PaintCtx, UiWidget};
 
 pub trait View<'a>: 'a {
     type Widget: Widget;
     fn build(self) -> Self::Widget;
     // widget is garaunteed to have same type_id as `widget_type_id`
-    fn update(self, widget: &mut Self::Widget);
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx);
 }
 
 trait DynView<'a>: 'a {
     fn build(&mut self) -> Box<dyn Widget>;
-    fn update(&mut self, widget: &mut dyn Widget);
+    fn update(&mut self, widget: &mut dyn Widget, ctx: &mut EventCtx);
 }
 
 struct DynViewWrap<V>(Option<V>);
@@ -24,11 +26,11 @@ impl<'a, V: View<'a>> DynView<'a> for DynViewWrap<V> {
         Box::new(self.0.take().unwrap().build())
     }
 
-    fn update(&mut self, widget: &mut dyn Widget) {
+    fn update(&mut self, widget: &mut dyn Widget, ctx: &mut EventCtx) {
         self.0
             .take()
             .unwrap()
-            .update(widget.as_any().downcast_mut::<V::Widget>().unwrap());
+            .update(widget.as_any().downcast_mut::<V::Widget>().unwrap(), ctx);
     }
 }
 
@@ -48,21 +50,27 @@ pub trait Widget: AsAny + 'static {
 
 pub struct AnyWidget {
     inner: Box<dyn Widget>,
+    widget_type_id: TypeId,
 }
 
 impl AnyWidget {
     pub fn new<W: Widget>(inner: W) -> Self {
         AnyWidget {
             inner: Box::new(inner),
+            widget_type_id: TypeId::of::<W>(),
         }
     }
 
+    pub fn widget_type_id(&self) -> TypeId {
+        self.widget_type_id
+    }
+
     pub fn as_ui_widget(&mut self) -> &mut dyn UiWidget {
         self.inner.as_ui_widget()
     }
 
-    pub fn update(&mut self, view: AnyView) {
-        view.update(self);
+    pub fn update(&mut self, view: AnyView, ctx: &mut EventCtx) {
+        view.update(self, ctx);
     }
 }
 
@@ -83,11 +91,29 @@ impl<'a> AnyView<'a> {
 
     pub fn build(mut self) -> AnyWidget {
         let widget = self.inner.build();
-        AnyWidget { inner: widget }
+        AnyWidget {
+            inner: widget,
+            widget_type_id: self.widget_type_id,
+        }
     }
 
-    pub fn update(mut self, widget: &mut AnyWidget) {
-        self.inner.update(&mut *widget.inner);
+    /// Update `widget` with this view, rebuilding it if the view now
+    /// produces a different widget type.
+    pub fn update(mut self, widget: &mut AnyWidget, ctx: &mut EventCtx) {
+        if !self.can_update(widget) {
+            widget.unmount(ctx);
+            ctx.state.forget_widget();
+            *widget = self.build();
+            widget.mount(ctx);
+            ctx.request_layout();
+        } else {
+            self.inner.update(&mut *widget.inner, ctx);
+        }
+    }
+
+    /// Whether this view can update `widget` in place.
+    pub fn can_update(&self, widget: &AnyWidget) -> bool {
+        self.widget_type_id == widget.widget_type_id
     }
 
     pub fn widget_type_id(&self) -> TypeId {
@@ -96,8 +122,12 @@ impl<'a> AnyView<'a> {
 }
 
 impl UiWidget for AnyWidget {
-    fn init(&mut self, ctx: &mut EventCtx) {
-        self.inner.as_ui_widget().init(ctx);
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.inner.as_ui_widget().mount(ctx);
+    }
+
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        self.inner.as_ui_widget().unmount(ctx);
     }
 
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
@@ -116,6 +146,14 @@ impl UiWidget for AnyWidget {
         self.inner.as_ui_widget().scroll(ctx, event);
     }
 
+    fn mouse_enter(&mut self, ctx: &mut EventCtx) {
+        self.inner.as_ui_widget().mouse_enter(ctx);
+    }
+
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        self.inner.as_ui_widget().mouse_leave(ctx);
+    }
+
     fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
         self.inner.as_ui_widget().key_down(ctx, event);
     }
@@ -128,6 +166,18 @@ impl UiWidget for AnyWidget {
         self.inner.as_ui_widget().timer(ctx, token);
     }
 
+    fn focus_changed(&mut self, ctx: &mut EventCtx, focused: bool) {
+        self.inner.as_ui_widget().focus_changed(ctx, focused);
+    }
+
+    fn for_each_host(
+        &mut self,
+        ctx: &mut EventCtx,
+        f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx),
+    ) {
+        self.inner.as_ui_widget().for_each_host(ctx, f);
+    }
+
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
         self.inner.as_ui_widget().layout(ctx, bc)
     }
@@ -135,4 +185,8 @@ impl UiWidget for AnyWidget {
     fn paint(&mut self, ctx: &mut PaintCtx) {
         self.inner.as_ui_widget().paint(ctx);
     }
+
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        self.inner.as_ui_widget().inspect(nodes);
+    }
 }
@@ -0,0 +1,93 @@
+use crate::inspect::InspectNode;
+use crate::kurbo::{Point, Rect, Size, Vec2};
+use crate::piet::{Color, Piet, RenderContext, Text, TextLayout, TextLayoutBuilder};
+
+/// The layout bounds overlay, see [`Window::set_debug_paint`].
+///
+/// [`Window::set_debug_paint`]: crate::Window::set_debug_paint
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DebugPaint {
+    /// Counts painted frames, to give every frame its own tint.
+    pub(crate) frame: u64,
+}
+
+const BOUNDS: Color = Color::rgba8(0x00, 0x80, 0xff, 0x80);
+const BAD_BOUNDS: Color = Color::rgb8(0xff, 0x00, 0x00);
+
+impl DebugPaint {
+    /// Outline a widget, in red if its layout went wrong.
+    ///
+    /// The widget is also tinted with the color of this frame, so widgets
+    /// that were not repainted keep the tint of an older frame.
+    pub(crate) fn paint_bounds(&self, piet: &mut Piet, rect: Rect, bad: bool) {
+        piet.fill(rect, &self.tint());
+        if bad {
+            piet.stroke(rect.inset(-1.0), &BAD_BOUNDS, 2.0);
+        } else {
+            piet.stroke(rect.inset(-0.5), &BOUNDS, 1.0);
+        }
+    }
+
+    fn tint(&self) -> Color {
+        let hue = (self.frame * 47 % 360) as f64;
+        Color::hlca(hue, 60.0, 60.0, 0.1)
+    }
+
+    /// Show the type and size of the innermost hovered widget of `tree`.
+    pub(crate) fn paint_overlay(&self, piet: &mut Piet, tree: &InspectNode, window_size: Size) {
+        let (node, origin) = match hovered(tree, Point::ZERO) {
+            Some(found) => found,
+            None => return,
+        };
+        let label = format!(
+            "{} {}x{}",
+            short_type_name(node.type_name),
+            node.size.width,
+            node.size.height
+        );
+        let layout = match piet
+            .text()
+            .new_text_layout(label)
+            .text_color(Color::WHITE)
+            .build()
+        {
+            Ok(layout) => layout,
+            Err(e) => {
+                eprintln!("Failed to lay out debug overlay: '{}'", e);
+                return;
+            }
+        };
+
+        let padding = Vec2::new(4.0, 2.0);
+        let size = layout.size() + Size::new(padding.x * 2.0, padding.y * 2.0);
+        // keep the label inside the window
+        let pos = Point::new(
+            origin.x.min(window_size.width - size.width).max(0.0),
+            origin.y.min(window_size.height - size.height).max(0.0),
+        );
+        piet.fill(
+            Rect::from_origin_size(pos, size),
+            &Color::rgba8(0, 0, 0, 0xc0),
+        );
+        piet.draw_text(&layout, pos + padding);
+    }
+}
+
+/// The innermost hovered node, and its origin in window coordinates.
+fn hovered(node: &InspectNode, parent_origin: Point) -> Option<(&InspectNode, Point)> {
+    if !node.hovered {
+        return None;
+    }
+    let origin = parent_origin + node.origin.to_vec2();
+    node.children
+        .iter()
+        .find_map(|child| hovered(child, origin))
+        .or(Some((node, origin)))
+}
+
+/// `olma::widgets::text::TextWidget` becomes `TextWidget`.
+fn short_type_name(name: &str) -> &str {
+    let path = name.split('<').next().unwrap_or(name);
+    let start = path.rfind("::").map_or(0, |i| i + 2);
+    &name[start..]
+}
@@ -0,0 +1,100 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+use druid_shell::IdleToken;
+
+pub(crate) const EXT_EVENT_IDLE_TOKEN: IdleToken = IdleToken::new(1);
+
+/// Delivers messages to the application from other threads.
+///
+/// Messages are queued, and picked up the next time the UI thread is idle.
+pub struct ExtEventSink<Msg> {
+    queue: Arc<Mutex<Queue>>,
+    _msg: PhantomData<fn(Msg)>,
+}
+
+impl<Msg> Clone for ExtEventSink<Msg> {
+    fn clone(&self) -> Self {
+        ExtEventSink {
+            queue: self.queue.clone(),
+            _msg: PhantomData,
+        }
+    }
+}
+
+impl<Msg> ExtEventSink<Msg> {
+    pub fn submit(&self, msg: Msg)
+    where
+        Msg: Send + 'static,
+    {
+        self.submit_any(Box::new(msg));
+    }
+
+    pub(crate) fn submit_any(&self, msg: Box<dyn Any + Send>) {
+        self.queue.lock().unwrap().push(msg);
+    }
+
+    pub(crate) fn cast<T>(self) -> ExtEventSink<T> {
+        ExtEventSink {
+            queue: self.queue,
+            _msg: PhantomData,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    messages: Vec<Box<dyn Any + Send>>,
+    wake: Option<Waker>,
+}
+
+/// Wakes up the run loop from any thread, see [`WindowHost::waker`].
+///
+/// [`WindowHost::waker`]: crate::WindowHost::waker
+pub type Waker = Box<dyn FnMut() + Send>;
+
+impl Queue {
+    fn push(&mut self, msg: Box<dyn Any + Send>) {
+        // an empty queue means nobody has woken up the run loop yet
+        if self.messages.is_empty() {
+            if let Some(wake) = self.wake.as_mut() {
+                wake();
+            }
+        }
+        self.messages.push(msg);
+    }
+}
+
+/// The receiving end of all the [`ExtEventSink`]s of an application.
+#[derive(Default)]
+pub(crate) struct ExtEventHost {
+    queue: Arc<Mutex<Queue>>,
+}
+
+impl ExtEventHost {
+    pub(crate) fn sink<Msg>(&self) -> ExtEventSink<Msg> {
+        ExtEventSink {
+            queue: self.queue.clone(),
+            _msg: PhantomData,
+        }
+    }
+
+    /// Start waking up the run loop through `wake`.
+    pub(crate) fn connect(&self, mut wake: Waker) {
+        let mut queue = self.queue.lock().unwrap();
+        if !queue.messages.is_empty() {
+            wake();
+        }
+        queue.wake = Some(wake);
+    }
+
+    pub(crate) fn drain(&self) -> Vec<Box<dyn Any>> {
+        let mut queue = self.queue.lock().unwrap();
+        queue
+            .messages
+            .drain(..)
+            .map(|msg| msg as Box<dyn Any>)
+            .collect()
+    }
+}
@@ -0,0 +1,237 @@
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::app::AppHolder;
+use crate::backend::{HeadlessWindow, WindowHost};
+use crate::ext_event::EXT_EVENT_IDLE_TOKEN;
+use crate::kurbo::{Point, Rect, Size, Vec2};
+use crate::inspect::InspectNode;
+use crate::launch::WindowConfig;
+use crate::piet::{Device, Error, ImageFormat, RenderContext};
+use crate::snapshot::Snapshot;
+use crate::window::{Window, WindowId};
+use crate::Application;
+
+use druid_shell::{
+    KeyEvent, Modifiers, MouseButton, MouseButtons, MouseEvent, Region, Scale, TimerToken,
+};
+
+/// Drives an [`Application`] without a display.
+///
+/// Events go through the same paths as in a real window, and the main window
+/// is laid out again after every one of them. Windows are backed by
+/// [`HeadlessWindow`]s.
+pub struct Harness<A> {
+    app: AppHolder,
+    main: Rc<HeadlessWindow>,
+    size: Size,
+    scale: Scale,
+    _app: PhantomData<A>,
+}
+
+impl<A: Application> Harness<A> {
+    pub fn new(app: A) -> Self {
+        Self::with_config(app, WindowConfig::default())
+    }
+
+    pub fn with_config(app: A, config: WindowConfig) -> Self {
+        let mut app = AppHolder::new(Box::new(app), &config);
+        let main = Rc::new(HeadlessWindow::new().expect("failed to create a headless window"));
+        app.connect(WindowId::Main, main.clone());
+        let mut harness = Harness {
+            app,
+            main,
+            size: config.size,
+            scale: Scale::default(),
+            _app: PhantomData,
+        };
+        harness.after_event();
+        harness
+    }
+
+    /// The application model.
+    pub fn app(&self) -> &A {
+        self.app
+            .app()
+            .as_any()
+            .downcast_ref()
+            .expect("app has the wrong type")
+    }
+
+    /// Lay out the main window at `size`.
+    pub fn layout(&mut self, size: impl Into<Size>) {
+        self.size = size.into();
+        self.after_event();
+    }
+
+    /// Lay out the main window again, as if it moved to a display with
+    /// `scale` device pixels per point. Rendering happens at that scale too.
+    pub fn set_scale(&mut self, scale: Scale) {
+        self.scale = scale;
+        self.after_event();
+    }
+
+    /// The size of the root widget after the last layout.
+    pub fn root_size(&mut self) -> Size {
+        self.window().root_size()
+    }
+
+    /// Paint the main window into a bitmap, at its current size.
+    pub fn render(&mut self) -> Result<Snapshot, Error> {
+        self.render_rect(self.size.to_rect())
+    }
+
+    /// Paint only what is inside `rect` of the main window, as the platform
+    /// does for a damaged region. The bitmap still covers the whole window.
+    pub fn render_rect(&mut self, rect: Rect) -> Result<Snapshot, Error> {
+        let width = (self.size.width * self.scale.x()).ceil() as usize;
+        let height = (self.size.height * self.scale.y()).ceil() as usize;
+        let region = Region::from(rect);
+
+        let mut device = Device::new()?;
+        let mut target = device.bitmap_target(width, height, self.scale.x())?;
+        {
+            let mut piet = target.render_context();
+            self.window().paint(&mut piet, &region);
+            piet.finish()?;
+        }
+        let mut pixels = vec![0; width * height * 4];
+        target.copy_raw_pixels(ImageFormat::RgbaSeparate, &mut pixels)?;
+        Ok(Snapshot {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// The open windows.
+    pub fn windows(&self) -> Vec<WindowId> {
+        self.app.window_ids()
+    }
+
+    /// Ask to close window `id`, like the close button of the platform.
+    pub fn request_close(&mut self, id: WindowId) {
+        self.app.request_close(id);
+        self.after_event();
+    }
+
+    /// The widget tree of the main window.
+    pub fn inspect(&mut self) -> InspectNode {
+        self.window().inspect()
+    }
+
+    pub fn mouse_move(&mut self, pos: impl Into<Point>) {
+        let event = mouse_event(pos.into(), MouseButton::None, 0);
+        self.with_window(|w| w.mouse_move(&event));
+    }
+
+    pub fn mouse_leave(&mut self) {
+        self.with_window(|w| w.mouse_leave());
+    }
+
+    pub fn mouse_down(&mut self, pos: impl Into<Point>, button: MouseButton) {
+        let event = mouse_event(pos.into(), button, 1);
+        self.with_window(|w| w.mouse_down(&event));
+    }
+
+    pub fn mouse_up(&mut self, pos: impl Into<Point>, button: MouseButton) {
+        let mut event = mouse_event(pos.into(), button, 0);
+        event.buttons = MouseButtons::new();
+        self.with_window(|w| w.mouse_up(&event));
+    }
+
+    /// Move the mouse to `pos` and click the left button.
+    pub fn click(&mut self, pos: impl Into<Point>) {
+        let pos = pos.into();
+        self.mouse_move(pos);
+        self.mouse_down(pos, MouseButton::Left);
+        self.mouse_up(pos, MouseButton::Left);
+    }
+
+    pub fn scroll(&mut self, pos: impl Into<Point>, delta: impl Into<Vec2>) {
+        let mut event = mouse_event(pos.into(), MouseButton::None, 0);
+        event.wheel_delta = delta.into();
+        self.with_window(|w| w.scroll(&event));
+    }
+
+    pub fn key_down(&mut self, event: KeyEvent) -> bool {
+        self.with_window(|w| w.key_down(event))
+    }
+
+    pub fn key_up(&mut self, event: KeyEvent) {
+        self.with_window(|w| w.key_up(event))
+    }
+
+    pub fn timer(&mut self, token: TimerToken) {
+        self.app.timer(WindowId::Main, token);
+        self.after_event();
+    }
+
+    /// The timers requested through the main window since the last call,
+    /// oldest first. Deliver them with [`timer`](Harness::timer).
+    pub fn take_timers(&mut self) -> Vec<TimerToken> {
+        self.main.take_timers()
+    }
+
+    /// Deliver the messages submitted through [`ExtEventSink`]s so far.
+    ///
+    /// [`ExtEventSink`]: crate::ExtEventSink
+    pub fn run_ext_events(&mut self) {
+        self.app.idle(EXT_EVENT_IDLE_TOKEN);
+        self.after_event();
+    }
+
+    fn window(&mut self) -> &mut Window {
+        self.app
+            .window_mut(WindowId::Main)
+            .expect("main window is closed")
+    }
+
+    fn with_window<R>(&mut self, f: impl FnOnce(&mut Window) -> R) -> R {
+        let r = f(self.window());
+        self.after_event();
+        r
+    }
+
+    /// What the shell handler does after every event, with windows opening
+    /// and closing right away.
+    fn after_event(&mut self) {
+        loop {
+            self.app.run_messages();
+            let pending = self.app.take_pending_windows();
+            let closing = self.app.take_closing_windows();
+            if pending.is_empty() && closing.is_empty() {
+                break;
+            }
+            for (id, _) in pending {
+                self.app.connect(id, headless());
+            }
+            for (id, _) in closing {
+                self.app.disconnect(id);
+            }
+        }
+
+        let (size, scale) = (self.size, self.scale);
+        if let Some(window) = self.app.window_mut(WindowId::Main) {
+            window.size_changed(size);
+            window.scale_changed(scale);
+            window.prepare_paint();
+        }
+    }
+}
+
+fn headless() -> Rc<dyn WindowHost> {
+    Rc::new(HeadlessWindow::new().expect("failed to create a headless window"))
+}
+
+fn mouse_event(pos: Point, button: MouseButton, count: u8) -> MouseEvent {
+    MouseEvent {
+        pos,
+        buttons: MouseButtons::new().with(button),
+        mods: Modifiers::default(),
+        count,
+        focus: false,
+        button,
+        wheel_delta: Vec2::ZERO,
+    }
+}
@@ -0,0 +1,118 @@
+use std::fmt::Write;
+
+use crate::kurbo::{Point, Size};
+use crate::BoxConstraints;
+
+/// One widget of the live widget tree, see [`Window::inspect`].
+///
+/// [`Window::inspect`]: crate::Window::inspect
+#[derive(Debug, Clone)]
+pub struct InspectNode {
+    pub type_name: &'static str,
+    pub origin: Point,
+    pub size: Size,
+    /// The constraints of the last layout, if there was one.
+    pub constraints: Option<BoxConstraints>,
+    pub hovered: bool,
+    pub mouse_focus: bool,
+    pub keyboard_focus: bool,
+    pub children: Vec<InspectNode>,
+}
+
+impl InspectNode {
+    /// An indented outline, with a line per widget.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        self.write_text(&mut out, 0);
+        out
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        self.write_json(&mut out);
+        out
+    }
+
+    fn write_text(&self, out: &mut String, depth: usize) {
+        let _ = write!(
+            out,
+            "{:indent$}{} origin=({}, {}) size={}x{}",
+            "",
+            self.type_name,
+            self.origin.x,
+            self.origin.y,
+            self.size.width,
+            self.size.height,
+            indent = depth * 2
+        );
+        if let Some(bc) = self.constraints {
+            let _ = write!(
+                out,
+                " bc=[{}x{}, {}x{}]",
+                bc.min().width,
+                bc.min().height,
+                bc.max().width,
+                bc.max().height
+            );
+        }
+        for (flag, name) in [
+            (self.hovered, "hovered"),
+            (self.mouse_focus, "mouse_focus"),
+            (self.keyboard_focus, "keyboard_focus"),
+        ] {
+            if flag {
+                let _ = write!(out, " {}", name);
+            }
+        }
+        out.push('\n');
+        for child in &self.children {
+            child.write_text(out, depth + 1);
+        }
+    }
+
+    fn write_json(&self, out: &mut String) {
+        let _ = write!(
+            out,
+            "{{\"type\":\"{}\",\"origin\":[{},{}],\"size\":[{},{}],\"constraints\":",
+            self.type_name.replace('\\', "\\\\").replace('"', "\\\""),
+            json_number(self.origin.x),
+            json_number(self.origin.y),
+            json_number(self.size.width),
+            json_number(self.size.height),
+        );
+        match self.constraints {
+            Some(bc) => {
+                let _ = write!(
+                    out,
+                    "{{\"min\":[{},{}],\"max\":[{},{}]}}",
+                    json_number(bc.min().width),
+                    json_number(bc.min().height),
+                    json_number(bc.max().width),
+                    json_number(bc.max().height),
+                );
+            }
+            None => out.push_str("null"),
+        }
+        let _ = write!(
+            out,
+            ",\"hovered\":{},\"mouse_focus\":{},\"keyboard_focus\":{},\"children\":[",
+            self.hovered, self.mouse_focus, self.keyboard_focus
+        );
+        for (i, child) in self.children.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            child.write_json(out);
+        }
+        out.push_str("]}");
+    }
+}
+
+/// JSON has no infinity, unbounded constraints become `null`.
+fn json_number(n: f64) -> String {
+    if n.is_finite() {
+        n.to_string()
+    } else {
+        String::from("null")
+    }
+}
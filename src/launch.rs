@@ -1,29 +1,124 @@
-use crate::kurbo::Size;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::kurbo::{Point, Size};
+use crate::window::WindowId;
 use crate::{app::AppHolder, AppDyn};
 
-use druid_shell::{Application, WindowBuilder};
+use druid_shell::{Application, Error, WinHandler, WindowBuilder, WindowHandle};
 
 use crate::shell_handler::ShellHandler;
 
-pub fn launch<A, Msg>(app: A)
+/// How a window should look when it is first opened.
+#[derive(Debug, Clone)]
+pub struct WindowConfig {
+    pub(crate) title: String,
+    pub(crate) size: Size,
+    pub(crate) min_size: Option<Size>,
+    pub(crate) position: Option<Point>,
+    pub(crate) resizable: bool,
+    pub(crate) transparent: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        WindowConfig {
+            title: String::from("Druidinho"),
+            size: Size::new(400., 400.),
+            min_size: None,
+            position: None,
+            resizable: true,
+            transparent: false,
+        }
+    }
+}
+
+impl WindowConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    pub fn min_size(mut self, size: impl Into<Size>) -> Self {
+        self.min_size = Some(size.into());
+        self
+    }
+
+    pub fn position(mut self, position: impl Into<Point>) -> Self {
+        self.position = Some(position.into());
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn transparent(mut self, transparent: bool) -> Self {
+        self.transparent = transparent;
+        self
+    }
+
+    pub(crate) fn build(
+        &self,
+        application: &Application,
+        handler: Box<dyn WinHandler>,
+    ) -> Result<WindowHandle, Error> {
+        let mut builder = WindowBuilder::new(application.clone());
+        builder.set_title(self.title.clone());
+        builder.set_size(self.size);
+        if let Some(min_size) = self.min_size {
+            builder.set_min_size(min_size);
+        }
+        if let Some(position) = self.position {
+            builder.set_position(position);
+        }
+        builder.resizable(self.resizable);
+        builder.set_transparent(self.transparent);
+        builder.set_handler(handler);
+        builder.build()
+    }
+}
+
+/// Open a window with the default [`WindowConfig`] and run `app` until it closes.
+pub fn launch<A, Msg>(app: A) -> Result<(), Error>
+where
+    Msg: 'static,
+    A: crate::Application<Msg = Msg> + 'static,
+{
+    launch_with(app, WindowConfig::default())
+}
+
+pub fn launch_with<A, Msg>(app: A, config: WindowConfig) -> Result<(), Error>
 where
     Msg: 'static,
     A: crate::Application<Msg = Msg> + 'static,
 {
     let app = Box::new(app);
-    _launch(app)
+    _launch(app, config)
 }
 
-fn _launch(app: Box<dyn AppDyn>) {
-    let application = Application::new().unwrap();
-    let holder = AppHolder::new(app);
+fn _launch(app: Box<dyn AppDyn>, config: WindowConfig) -> Result<(), Error> {
+    let application = Application::new()?;
+    let holder = Rc::new(RefCell::new(AppHolder::new(app, &config)));
 
-    let handler = ShellHandler::new(holder);
-    let mut builder = WindowBuilder::new(application.clone());
-    builder.set_title("Druidinho");
-    builder.set_size(Size::new(400., 400.));
-    builder.set_handler(Box::new(handler));
-    let window = builder.build().unwrap();
+    let handler = ShellHandler::new(holder.clone(), WindowId::Main);
+    let window = config.build(&application, Box::new(handler))?;
     window.show();
+    let windows = holder.borrow_mut().take_pending_windows();
+    for (id, config) in windows {
+        let handler = ShellHandler::new(holder.clone(), id);
+        config.build(&application, Box::new(handler))?.show();
+    }
     application.run(None);
+    Ok(())
 }
@@ -1,11 +1,18 @@
 #![allow(clippy::needless_lifetimes, dead_code)]
 
+mod backend;
 mod box_constraints;
+mod command;
+mod ext_event;
+mod harness;
+mod inspect;
 mod view_bump;
 mod contexts;
+mod debug_paint;
 mod launch;
 mod mouse;
 mod shell_handler;
+mod snapshot;
 mod widget;
 mod app;
 mod view_ext;
@@ -15,13 +22,19 @@ pub mod widgets;
 mod ui_widgets;
 mod window;
 
-pub use app::{Application, AppDyn};
+pub use app::{Application, AppDyn, WindowView};
+pub use backend::{HeadlessWindow, WindowHost};
 pub use box_constraints::BoxConstraints;
+pub use command::Command;
+pub use ext_event::ExtEventSink;
 pub use contexts::{EventCtx, LayoutCtx, PaintCtx};
-pub use launch::launch;
+pub use harness::Harness;
+pub use inspect::InspectNode;
+pub use launch::{launch, launch_with, WindowConfig};
 pub use mouse::MouseEvent;
+pub use snapshot::Snapshot;
 pub use widget::UiWidget;
-pub use window::Window;
+pub use window::{Window, WindowId};
 pub use view_ext::ViewExt;
 
 pub use druid_shell::{self as shell, kurbo, piet};
@@ -1,93 +1,110 @@
 use std::any::Any;
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::time::Duration;
 
-use crate::piet::Piet;
+use crate::backend::WindowHost;
+use crate::ext_event::{Waker, EXT_EVENT_IDLE_TOKEN};
+use crate::kurbo::Rect;
+use crate::piet::{Piet, PietText};
+use crate::window::WindowId;
 use crate::{app::AppHolder, kurbo::Size};
 
 use druid_shell::{
-    FileDialogToken, FileInfo, IdleToken, KeyEvent, MouseEvent, Region, Scale, TextFieldToken,
-    TimerToken, WinHandler, WindowHandle,
+    Application, Cursor, FileDialogToken, FileInfo, IdleToken, KeyEvent, MouseEvent, Region, Scale,
+    TextFieldToken, TimerToken, WinHandler, WindowHandle,
 };
 
 use super::Window;
 
-pub struct ShellHandler {
-    //app: Application,
-    inner: WindowConnection,
-}
+impl WindowHost for WindowHandle {
+    fn invalidate(&self) {
+        WindowHandle::invalidate(self)
+    }
 
-enum WindowConnection {
-    Waiting(AppHolder),
-    Connected(Rc<RefCell<Window>>),
-    Closed,
-    // a sentinel state only used during transitions
-    Invalid,
-}
+    fn invalidate_rect(&self, rect: Rect) {
+        WindowHandle::invalidate_rect(self, rect)
+    }
 
-impl WindowConnection {
-    fn transition(&mut self) -> WindowConnection {
-        std::mem::replace(self, WindowConnection::Invalid)
+    fn text(&self) -> PietText {
+        WindowHandle::text(self)
+    }
+
+    fn request_timer(&self, deadline: Duration) -> TimerToken {
+        WindowHandle::request_timer(self, deadline)
+    }
+
+    fn set_cursor(&self, cursor: &Cursor) {
+        WindowHandle::set_cursor(&mut self.clone(), cursor)
+    }
+
+    fn set_title(&self, title: &str) {
+        WindowHandle::set_title(self, title)
+    }
+
+    fn close(&self) {
+        WindowHandle::close(self)
+    }
+
+    fn waker(&self) -> Option<Waker> {
+        let mut idle = self.get_idle_handle()?;
+        Some(Box::new(move || idle.schedule_idle(EXT_EVENT_IDLE_TOKEN)))
     }
 }
 
+pub struct ShellHandler {
+    app: Rc<RefCell<AppHolder>>,
+    window: WindowId,
+}
+
 impl ShellHandler {
-    pub fn new(widget: AppHolder) -> Self {
-        ShellHandler {
-            inner: WindowConnection::Waiting(widget),
-        }
+    pub fn new(app: Rc<RefCell<AppHolder>>, window: WindowId) -> Self {
+        ShellHandler { app, window }
     }
 
     fn after_event(&mut self) {
-        if let WindowConnection::Connected(window) = &mut self.inner {
-
-            if !window.borrow().messages.is_empty() {
-                window.borrow_mut().update();
-                // TODO: handle messages
-                // app.update(&window.borrow().messages);
-                // window.borrow_mut().messages.clear();
+        let (pending, closing) = {
+            let mut app = self.app.borrow_mut();
+            app.run_messages();
+            (app.take_pending_windows(), app.take_closing_windows())
+        };
+        // the app must not be borrowed here, windows may connect or get
+        // destroyed right away
+        for (_, handle) in closing {
+            handle.close();
+        }
+        for (id, config) in pending {
+            let handler = ShellHandler::new(self.app.clone(), id);
+            match config.build(&Application::global(), Box::new(handler)) {
+                Ok(window) => window.show(),
+                Err(e) => eprintln!("Failed to open window: {}", e),
             }
-            // window.borrow_mut().update();
         }
     }
 
-    //fn with_window<R>(&self, f: impl FnOnce(&Window) -> R) -> Option<R> {
-    //match &self.inner {
-    //WindowConnection::Connected(w) => Some(f(&*w.borrow())),
-    //_ => {
-    //eprintln!("missing window");
-    //None
-    //}
-    //}
-    //}
-
     fn with_window_mut<R>(&mut self, f: impl FnOnce(&mut Window) -> R) -> Option<R> {
-        match &mut self.inner {
-            WindowConnection::Connected(window) => {
-                let r = Some(f(&mut *window.borrow_mut()));
-                self.after_event();
-                r
-            }
-            _ => {
+        let r = match self.app.borrow_mut().window_mut(self.window) {
+            Some(window) => Some(f(window)),
+            None => {
                 eprintln!("missing window");
                 None
             }
-        }
+        };
+        self.after_event();
+        r
     }
 }
 
 impl WinHandler for ShellHandler {
     fn connect(&mut self, handle: &WindowHandle) {
-        self.inner = match self.inner.transition() {
-            WindowConnection::Waiting(app) => {
-                let window = Window::new(handle.clone(), app);
-                WindowConnection::Connected(Rc::new(RefCell::new(window)))
-            }
-            WindowConnection::Connected { .. } => panic!("window already connected"),
-            WindowConnection::Closed => panic!("window has been closed"),
-            WindowConnection::Invalid => unreachable!(),
-        };
-        self.with_window_mut(|w| w.window_connected());
+        let scale = handle.get_scale();
+        let handle = Rc::new(handle.clone());
+        self.app.borrow_mut().connect(self.window, handle);
+        // later changes come through `scale`
+        if let Ok(scale) = scale {
+            self.with_window_mut(|w| w.scale_changed(scale));
+        }
+        self.after_event();
     }
 
     fn prepare_paint(&mut self) {
@@ -105,8 +122,8 @@ impl WinHandler for ShellHandler {
         //self.app_state.do_window_event(event, self.window_id);
     }
 
-    fn scale(&mut self, _scale: Scale) {
-        // TODO: Do something with the scale
+    fn scale(&mut self, scale: Scale) {
+        self.with_window_mut(|w| w.scale_changed(scale));
     }
 
     fn command(&mut self, _id: u32) {}
@@ -132,8 +149,7 @@ impl WinHandler for ShellHandler {
     }
 
     fn mouse_leave(&mut self) {
-        //self.app_state
-        //.do_window_event(Event::Internal(InternalEvent::MouseLeave), self.window_id);
+        self.with_window_mut(Window::mouse_leave);
     }
 
     fn key_down(&mut self, event: KeyEvent) -> bool {
@@ -166,13 +182,15 @@ impl WinHandler for ShellHandler {
     }
 
     fn timer(&mut self, token: TimerToken) {
-        self.with_window_mut(|w| w.timer(token));
+        self.app.borrow_mut().timer(self.window, token);
+        self.after_event();
         //self.app_state
         //.do_window_event(Event::Timer(token), self.window_id);
     }
 
     fn idle(&mut self, token: IdleToken) {
-        self.with_window_mut(|w| w.idle(token));
+        self.app.borrow_mut().idle(token);
+        self.after_event();
         //self.app_state.idle(token);
     }
 
@@ -196,15 +214,22 @@ impl WinHandler for ShellHandler {
     }
 
     fn request_close(&mut self) {
-
-        //self.app_state
-        //.handle_cmd(sys_cmd::CLOSE_WINDOW.to(self.window_id));
-        //self.app_state.process_commands();
-        //self.app_state.inner.borrow_mut().do_update();
+        self.app.borrow_mut().request_close(self.window);
+        self.after_event();
     }
 
     fn destroy(&mut self) {
-        self.inner = WindowConnection::Closed;
+        let has_windows = {
+            let mut app = self.app.borrow_mut();
+            app.disconnect(self.window);
+            app.has_windows()
+        };
+        if has_windows {
+            // deliver what the window's widgets sent while unmounting
+            self.after_event();
+        } else {
+            Application::global().quit();
+        }
         //self.app_state.remove_window(self.window_id);
     }
 }
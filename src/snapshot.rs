@@ -0,0 +1,147 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+/// An RGBA image of a rendered window, see [`Harness::render`].
+///
+/// [`Harness::render`]: crate::Harness::render
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    /// Rows of RGBA pixels, not premultiplied.
+    pub(crate) pixels: Vec<u8>,
+}
+
+impl Snapshot {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> [u8; 4] {
+        let i = (y * self.width + x) * 4;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), png::EncodingError> {
+        let file = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.pixels)
+    }
+
+    pub fn load_png(path: impl AsRef<Path>) -> Result<Self, png::DecodingError> {
+        let file = BufReader::new(File::open(path)?);
+        let mut decoder = png::Decoder::new(file);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder.read_info()?;
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels)?;
+        pixels.truncate(info.buffer_size());
+        // after the transformations, this is 8 bit gray or rgb, with or without alpha
+        let pixels = match info.color_type {
+            png::ColorType::Rgb => pixels
+                .chunks(3)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect(),
+            png::ColorType::Grayscale => pixels.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+            png::ColorType::GrayscaleAlpha => pixels
+                .chunks(2)
+                .flat_map(|p| [p[0], p[0], p[0], p[1]])
+                .collect(),
+            _ => pixels,
+        };
+        Ok(Snapshot {
+            width: info.width as usize,
+            height: info.height as usize,
+            pixels,
+        })
+    }
+
+    /// The number of pixels with a channel that is more than `tolerance` off,
+    /// or `None` if the sizes differ.
+    pub fn diff(&self, other: &Snapshot, tolerance: u8) -> Option<usize> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return None;
+        }
+        let differing = self
+            .pixels
+            .chunks(4)
+            .zip(other.pixels.chunks(4))
+            .filter(|(a, b)| {
+                a.iter()
+                    .zip(b.iter())
+                    .any(|(a, b)| a.abs_diff(*b) > tolerance)
+            })
+            .count();
+        Some(differing)
+    }
+
+    /// Compare against the golden image at `path`.
+    ///
+    /// The golden image is written instead when `OLMA_BLESS` is set. On a
+    /// mismatch, or when there is no golden image, the snapshot is saved next to
+    /// it with an `.actual.png` extension, and this panics.
+    pub fn assert_golden(&self, path: impl AsRef<Path>, tolerance: u8) {
+        let path = path.as_ref();
+        if std::env::var_os("OLMA_BLESS").is_some() {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).unwrap();
+            }
+            self.save_png(path).unwrap();
+            return;
+        }
+        if !path.exists() {
+            let actual = PathBuf::from(path).with_extension("actual.png");
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).unwrap();
+            }
+            self.save_png(&actual).unwrap();
+            panic!(
+                "no golden image at {}, see {} and run with OLMA_BLESS=1 to accept it",
+                path.display(),
+                actual.display()
+            );
+        }
+
+        let golden = Snapshot::load_png(path).unwrap();
+        match self.diff(&golden, tolerance) {
+            Some(0) => {}
+            diff => {
+                let actual = PathBuf::from(path).with_extension("actual.png");
+                self.save_png(&actual).unwrap();
+                match diff {
+                    Some(n) => panic!(
+                        "{} pixels differ from {}, see {}",
+                        n,
+                        path.display(),
+                        actual.display()
+                    ),
+                    None => panic!(
+                        "size {}x{} differs from {}x{} of {}, see {}",
+                        self.width,
+                        self.height,
+                        golden.width,
+                        golden.height,
+                        path.display(),
+                        actual.display()
+                    ),
+                }
+            }
+        }
+    }
+}
@@ -18,17 +18,20 @@ use std::any::Any;
 use std::borrow::Cow;
 
 use crate::core::AnyView;
+use crate::inspect::InspectNode;
 use crate::kurbo::{Insets, Point, Size};
 use crate::piet::{Color, RenderContext};
 use crate::widgets::layout::LayoutHost;
 use crate::widgets::text::Text;
+use crate::shell::{KbKey, KeyEvent};
 use crate::{BoxConstraints, EventCtx, LayoutCtx, MouseEvent, PaintCtx, UiWidget};
 
+const FOCUS_COLOR: Color = Color::rgb8(0x3b, 0x82, 0xf6);
+
 /// A widget that provides simple visual styling options to a child.
 pub struct Button {
     pub(crate) text: LayoutHost,
     pub(crate) on_click: Option<Box<dyn Fn() -> Box<dyn Any>>>,
-    pub(crate) hovered: bool,
 }
 
 impl Button {
@@ -36,7 +39,6 @@ impl Button {
         Button {
             text: LayoutHost::new(AnyView::new(Text::new(text)).build()),
             //on_click: None,
-            hovered: false,
             on_click: None,
         }
     }
@@ -48,28 +50,50 @@ impl Button {
 }
 
 impl UiWidget for Button {
-    fn init(&mut self, ctx: &mut EventCtx) {
-        self.text.init(ctx);
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        ctx.register_for_focus();
+        self.text.mount(ctx);
     }
 
-    fn mouse_move(&mut self, ctx: &mut EventCtx, _event: &MouseEvent) {
-        if ctx.hovered() != self.hovered {
-            ctx.request_paint();
-            self.hovered = ctx.hovered();
-        }
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        self.text.unmount(ctx);
+    }
+
+    fn mouse_enter(&mut self, ctx: &mut EventCtx) {
+        ctx.request_paint();
+    }
+
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        ctx.request_paint();
     }
 
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         if event.button.is_left() {
             ctx.set_mouse_focus(true);
+            ctx.request_focus();
             ctx.request_paint();
+            ctx.set_handled();
         }
     }
 
+    fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
+        if event.key == KbKey::Enter || event.key == KbKey::Character(" ".into()) {
+            if let Some(c) = self.on_click.as_ref() {
+                ctx.submit_message(c());
+            }
+            ctx.set_handled();
+        }
+    }
+
+    fn focus_changed(&mut self, ctx: &mut EventCtx, _focused: bool) {
+        ctx.request_paint();
+    }
+
     fn mouse_up(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         if event.button.is_left() && ctx.mouse_focused() {
             ctx.request_paint();
             ctx.set_mouse_focus(false);
+            ctx.set_handled();
             if ctx.hovered() {
                 if let Some(c) = self.on_click.as_ref() {
                     ctx.submit_message(c());
@@ -87,17 +111,29 @@ impl UiWidget for Button {
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx) {
+        const STROKE_WIDTH: f64 = 2.0;
         let rect = ctx.frame().to_rounded_rect(2.0);
         if ctx.hovered() || ctx.mouse_focused() {
             ctx.fill(rect, &Color::GRAY);
         } else {
             ctx.fill(rect, &Color::WHITE);
         }
+        // keep the stroke inside the frame, on whole pixels
+        let stroke_rect = ctx
+            .frame()
+            .inset(STROKE_WIDTH / -2.0)
+            .to_rounded_rect(2.0);
         if ctx.mouse_focused() {
-            ctx.stroke(rect, &Color::BLACK, 2.0);
+            ctx.stroke(stroke_rect, &Color::BLACK, STROKE_WIDTH);
+        } else if ctx.keyboard_focused() {
+            ctx.stroke(stroke_rect, &FOCUS_COLOR, STROKE_WIDTH);
         } else {
-            ctx.stroke(rect, &Color::GRAY, 2.0);
+            ctx.stroke(stroke_rect, &Color::GRAY, STROKE_WIDTH);
         }
         self.text.paint(ctx);
     }
+
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        self.text.inspect(nodes);
+    }
 }
@@ -80,7 +80,7 @@ impl Text {
 }
 
 impl UiWidget for Text {
-    fn init(&mut self, ctx: &mut EventCtx) {
+    fn mount(&mut self, ctx: &mut EventCtx) {
         self.rebuild_text(ctx.text())
     }
 
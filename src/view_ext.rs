@@ -1,12 +1,85 @@
-use druid_shell::piet::Color;
+use druid_shell::piet::{Color, UnitPoint};
 
-use crate::{core::*, widgets::background::Background};
+use crate::widget::Never;
+use crate::widgets::{Align, Background, Map, RepaintBoundary, SizedBox};
+use crate::{core::*, EventCtx};
 
 pub trait ViewExt<'a>: View<'a> + Sized {
+    /// Wrap this view in a [`SizedBox`] with an explicit width.
+    fn fix_width(self, width: f64) -> SizedBox<'a> {
+        SizedBox::new(self).width(width)
+    }
+
+    /// Wrap this view in a [`SizedBox`] with an explicit height.
+    fn fix_height(self, height: f64) -> SizedBox<'a> {
+        SizedBox::new(self).height(height)
+    }
+
+    /// Wrap this view in an [`SizedBox`] with an explicit width and height
+    fn fix_size(self, width: f64, height: f64) -> SizedBox<'a> {
+        SizedBox::new(self).width(width).height(height)
+    }
+
+    /// Wrap this view in an [`Align`], configured to center it.
+    fn center(self) -> Align<'a> {
+        Align::new(self).centered()
+    }
+
+    /// Wrap this view in an [`Align`] with the given alignment.
+    fn align(self, align: UnitPoint) -> Align<'a> {
+        Align::new(self).alignment(align)
+    }
+
     fn background(self, color: Color) -> Background<'a> {
         Background::new(self).background(color)
     }
 
+    fn border(self, color: Color, width: f64) -> Background<'a> {
+        Background::new(self).border(color, width)
+    }
+
+    /// Cache the painted output of this view, see [`RepaintBoundary`].
+    fn repaint_boundary(self) -> RepaintBoundary<'a> {
+        RepaintBoundary::new(self)
+    }
+
+    /// Turn the messages of type `T` emitted by this view into `U`.
+    fn map_msg<T: 'static, U: 'static>(self, map: impl Fn(T) -> U + 'static) -> Map<'a> {
+        Map::new(map, self)
+    }
+
+    fn map_actions<In: 'static, Out: 'static>(
+        self,
+        map: impl FnMut(In) -> Out + 'static,
+    ) -> Map<'a> {
+        Map::new(map, self)
+    }
+
+    fn filter_map_actions<In: 'static, Out: 'static>(
+        self,
+        mut map: impl FnMut(In) -> Option<Out> + 'static,
+    ) -> Map<'a> {
+        Map::filter_map(move |x, _| map(x), self)
+    }
+
+    fn suppress_actions<In: 'static>(self) -> Map<'a> {
+        Map::filter_map(|_: In, _| None::<Never>, self)
+    }
+
+    /// Handle all actions of type `In`.
+    fn handle_actions<In: 'static>(
+        self,
+        mut f: impl FnMut(In, &mut EventCtx) + 'static,
+    ) -> Map<'a> {
+        Map::filter_map(
+            move |x, ctx| {
+                f(x, ctx);
+                None::<Never>
+            },
+            self,
+        )
+    }
+
     fn any(self) -> AnyView<'a> {
         AnyView::new(self)
     }
@@ -1,3 +1,5 @@
+use crate::inspect::InspectNode;
+use crate::widget_host::WidgetHost;
 use crate::kurbo::Size;
 use crate::{BoxConstraints, EventCtx, LayoutCtx, MouseEvent, PaintCtx};
 use druid_shell::{KeyEvent, TimerToken};
@@ -6,18 +8,38 @@ pub enum Never {}
 
 #[allow(unused_variables)]
 pub trait UiWidget {
-    fn init(&mut self, ctx: &mut EventCtx) {}
+    /// Called once the widget has been added to the tree.
+    fn mount(&mut self, ctx: &mut EventCtx) {}
+    /// Called right before the widget is removed from the tree.
+    fn unmount(&mut self, ctx: &mut EventCtx) {}
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {}
     fn mouse_up(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {}
     fn mouse_move(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {}
     fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {}
+    /// The mouse moved into the widget's frame.
+    fn mouse_enter(&mut self, ctx: &mut EventCtx) {}
+    /// The mouse left the widget's frame, or the window.
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {}
     fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {}
     fn key_up(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {}
     fn timer(&mut self, ctx: &mut EventCtx, token: TimerToken) {}
+    /// Keyboard focus moved to or away from this widget.
+    fn focus_changed(&mut self, ctx: &mut EventCtx, focused: bool) {}
+    /// Call `f` with the outermost [`WidgetHost`]s below this widget.
+    ///
+    /// Widgets with children have to forward this, otherwise focus can't
+    /// reach the widgets below them.
+    fn for_each_host(&mut self, ctx: &mut EventCtx, f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx)) {}
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
         Size::ZERO
     }
     fn paint(&mut self, ctx: &mut PaintCtx) {}
+    /// The name of the widget in the inspector.
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+    /// Add the nodes of the child widgets to `nodes`, for the inspector.
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {}
 }
 
 /// The null widget, which does nothing.
@@ -40,8 +62,11 @@ pub trait SingleChildContainer {
 
     fn widget_mut(&mut self) -> &mut Self::Child;
 
-    fn init(&mut self, ctx: &mut EventCtx) {
-        self.widget_mut().init(ctx)
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.widget_mut().mount(ctx)
+    }
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        self.widget_mut().unmount(ctx)
     }
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         self.widget_mut().mouse_down(ctx, event)
@@ -55,6 +80,12 @@ pub trait SingleChildContainer {
     fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         self.widget_mut().scroll(ctx, event)
     }
+    fn mouse_enter(&mut self, ctx: &mut EventCtx) {
+        self.widget_mut().mouse_enter(ctx)
+    }
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        self.widget_mut().mouse_leave(ctx)
+    }
     fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
         self.widget_mut().key_down(ctx, event)
     }
@@ -64,17 +95,29 @@ pub trait SingleChildContainer {
     fn timer(&mut self, ctx: &mut EventCtx, token: TimerToken) {
         self.widget_mut().timer(ctx, token)
     }
+    fn focus_changed(&mut self, ctx: &mut EventCtx, focused: bool) {
+        self.widget_mut().focus_changed(ctx, focused)
+    }
+    fn for_each_host(&mut self, ctx: &mut EventCtx, f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx)) {
+        self.widget_mut().for_each_host(ctx, f)
+    }
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
         self.widget_mut().layout(ctx, bc)
     }
     fn paint(&mut self, ctx: &mut PaintCtx) {
         self.widget_mut().paint(ctx)
     }
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        self.widget_mut().inspect(nodes)
+    }
 }
 
 impl<T: SingleChildContainer<Child = W>, W: UiWidget> UiWidget for T {
-    fn init(&mut self, ctx: &mut EventCtx) {
-        <Self as SingleChildContainer>::init(self, ctx)
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        <Self as SingleChildContainer>::mount(self, ctx)
+    }
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        <Self as SingleChildContainer>::unmount(self, ctx)
     }
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         <Self as SingleChildContainer>::mouse_down(self, ctx, event)
@@ -88,6 +131,12 @@ impl<T: SingleChildContainer<Child = W>, W: UiWidget> UiWidget for T {
     fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         <Self as SingleChildContainer>::scroll(self, ctx, event)
     }
+    fn mouse_enter(&mut self, ctx: &mut EventCtx) {
+        <Self as SingleChildContainer>::mouse_enter(self, ctx)
+    }
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        <Self as SingleChildContainer>::mouse_leave(self, ctx)
+    }
     fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
         <Self as SingleChildContainer>::key_down(self, ctx, event)
     }
@@ -97,10 +146,19 @@ impl<T: SingleChildContainer<Child = W>, W: UiWidget> UiWidget for T {
     fn timer(&mut self, ctx: &mut EventCtx, token: TimerToken) {
         <Self as SingleChildContainer>::timer(self, ctx, token)
     }
+    fn focus_changed(&mut self, ctx: &mut EventCtx, focused: bool) {
+        <Self as SingleChildContainer>::focus_changed(self, ctx, focused)
+    }
+    fn for_each_host(&mut self, ctx: &mut EventCtx, f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx)) {
+        <Self as SingleChildContainer>::for_each_host(self, ctx, f)
+    }
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
         <Self as SingleChildContainer>::layout(self, ctx, bc)
     }
     fn paint(&mut self, ctx: &mut PaintCtx) {
         <Self as SingleChildContainer>::paint(self, ctx)
     }
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        <Self as SingleChildContainer>::inspect(self, nodes)
+    }
 }
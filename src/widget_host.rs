@@ -1,20 +1,53 @@
-use std::ops::DerefMut;
+use std::collections::HashSet;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use crate::core::{AnyView, AnyWidget};
 use crate::kurbo::{Point, Size};
 use druid_shell::{KeyEvent, TimerToken};
 
 use crate::contexts::{EventCtx, LayoutCtx, PaintCtx};
+use crate::inspect::InspectNode;
+use crate::widgets::keyed::Key;
 use crate::widgets::layout::LayoutHost;
 use crate::{BoxConstraints, MouseEvent, UiWidget};
 
 pub struct WidgetHost {
     child: LayoutHost,
     state: WidgetState,
+    pub(crate) key: Option<Key>,
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Identity of a [`WidgetHost`], unique within the process.
+///
+/// The default id belongs to no widget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct WidgetId(u64);
+
+impl WidgetId {
+    fn next() -> Self {
+        WidgetId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A change of keyboard focus, carried up to the window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum FocusChange {
+    /// Focus the widget.
+    Focus(WidgetId),
+    /// Nothing has focus.
+    Resign,
+    /// The next widget in the focus chain.
+    Next,
+    /// The previous widget in the focus chain.
+    Previous,
 }
 
 #[derive(Debug, Clone, Default)]
 pub(crate) struct WidgetState {
+    pub(crate) id: WidgetId,
     ///// The mouse is inside the widget's frame.
     //pub(crate) hovered: bool,
     /// The widget has mouse focus.
@@ -26,8 +59,26 @@ pub(crate) struct WidgetState {
     /// The widget has keyboard focus
     pub(crate) keyboard_focus: bool,
     /// A descendent of the widget has keyboard focus
+    ///
+    /// Only recomputed when focus moves, so it survives events that don't
+    /// visit every child.
     child_keyboard_focus: bool,
+    /// The widget registered for focus when it was mounted.
+    pub(crate) focusable: bool,
+    /// Pending timers of the widget and its descendents.
+    pub(crate) timers: HashSet<TimerToken>,
+    /// Timers of unmounted descendents, still to be removed from the
+    /// ancestors' `timers`.
+    pub(crate) dropped_timers: Vec<TimerToken>,
+    /// The widget or a descendent handled the current event.
+    pub(crate) is_handled: bool,
+    /// A focus change requested by the widget or a descendent.
+    pub(crate) request_focus: Option<FocusChange>,
     pub(crate) request_update: bool,
+    /// The widget or a descendent has to be laid out again.
+    pub(crate) needs_layout: bool,
+    /// The widget or a descendent has to be painted again.
+    pub(crate) needs_paint: bool,
 }
 
 impl WidgetState {
@@ -35,10 +86,33 @@ impl WidgetState {
         self.mouse_focus || self.child_mouse_focus
     }
 
+    /// Forget what the widget registered, before the host gets a new one.
+    ///
+    /// The widgets in nested hosts take care of themselves when unmounted.
+    pub(crate) fn forget_widget(&mut self) {
+        if self.keyboard_focus {
+            self.keyboard_focus = false;
+            self.request_focus = Some(FocusChange::Resign);
+        }
+        self.focusable = false;
+        self.dropped_timers.extend(self.timers.drain());
+    }
+
     fn merge_up(&mut self, child: &mut WidgetState) {
         self.child_mouse_focus |= child.child_mouse_focus | child.mouse_focus;
         self.child_keyboard_focus |= child.child_keyboard_focus | child.keyboard_focus;
         self.request_update |= child.request_update;
+        self.needs_layout |= child.needs_layout;
+        self.needs_paint |= child.needs_paint;
+        self.is_handled |= child.is_handled;
+        self.timers.extend(child.timers.iter().copied());
+        for token in child.dropped_timers.drain(..) {
+            self.timers.remove(&token);
+            self.dropped_timers.push(token);
+        }
+        if let Some(change) = child.request_focus.take() {
+            self.request_focus = Some(change);
+        }
     }
 }
 
@@ -46,20 +120,69 @@ impl WidgetHost {
     pub fn new(child: AnyWidget) -> Self {
         WidgetHost {
             child: LayoutHost::new(child),
-            state: Default::default(),
+            state: WidgetState {
+                id: WidgetId::next(),
+                ..Default::default()
+            },
+            key: None,
         }
     }
 
+    pub(crate) fn with_key(mut self, key: Option<Key>) -> Self {
+        self.key = key;
+        self
+    }
+
     pub fn set_origin(&mut self, origin: Point) {
         self.child.set_origin(origin);
     }
 
-    pub fn update<'b>(&mut self, view: AnyView<'b>) {
-        /*if view.type_id() == self.child.child.view_type_id() {*/
-        self.child.update(view);
-        /*} else {
-            todo!()
-        }*/
+    pub fn size(&self) -> Size {
+        self.child.size()
+    }
+
+    /// Whether anything in this subtree requested paint since it was last
+    /// painted.
+    pub(crate) fn needs_paint(&self) -> bool {
+        self.state.needs_paint
+    }
+
+    /// Update the child, see [`AnyView::update`].
+    pub fn update<'b>(&mut self, view: AnyView<'b>, ctx: &mut EventCtx) {
+        self.with_child(ctx, |chld, ctx| chld.update(view, ctx));
+    }
+
+    /// Call `f` with this host and every host below it, in tree order.
+    fn walk(&mut self, ctx: &mut EventCtx, f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx)) {
+        f(self, ctx);
+        self.with_child(ctx, |chld, ctx| {
+            chld.for_each_host(ctx, &mut |host, ctx| host.walk(ctx, f))
+        });
+    }
+
+    /// The focusable widgets in this subtree, in tree order.
+    pub(crate) fn focus_chain(&mut self, ctx: &mut EventCtx) -> Vec<WidgetId> {
+        let mut chain = Vec::new();
+        self.walk(ctx, &mut |host, _| {
+            if host.state.focusable {
+                chain.push(host.state.id);
+            }
+        });
+        chain
+    }
+
+    /// Give keyboard focus to `focus` and take it from everyone else,
+    /// notifying the widgets that gained or lost it.
+    pub(crate) fn set_focus(&mut self, ctx: &mut EventCtx, focus: Option<WidgetId>) {
+        self.walk(ctx, &mut |host, ctx| {
+            // the children merge it back in when they are walked
+            host.state.child_keyboard_focus = false;
+            let focused = focus == Some(host.state.id);
+            if host.state.keyboard_focus != focused {
+                host.state.keyboard_focus = focused;
+                host.with_child(ctx, |chld, ctx| chld.focus_changed(ctx, focused));
+            }
+        });
     }
 
     fn with_child<R>(
@@ -67,8 +190,8 @@ impl WidgetHost {
         parent_ctx: &mut EventCtx,
         f: impl FnOnce(&mut LayoutHost, &mut EventCtx) -> R,
     ) -> R {
-        self.state.child_keyboard_focus = false;
         self.state.child_mouse_focus = false;
+        self.state.is_handled = false;
 
         let mut child_ctx = EventCtx {
             state: &mut self.state,
@@ -83,8 +206,20 @@ impl WidgetHost {
 }
 
 impl UiWidget for WidgetHost {
-    fn init(&mut self, ctx: &mut EventCtx) {
-        self.with_child(ctx, |chld, ctx| chld.init(ctx))
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.with_child(ctx, |chld, ctx| chld.mount(ctx))
+    }
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        let had_focus = self.state.keyboard_focus || self.state.child_keyboard_focus;
+        self.with_child(ctx, |chld, ctx| chld.unmount(ctx));
+        if had_focus {
+            ctx.state.request_focus = Some(FocusChange::Resign);
+        }
+        // the timers can't reach the widgets anymore
+        for token in self.state.timers.drain() {
+            ctx.state.timers.remove(&token);
+            ctx.state.dropped_timers.push(token);
+        }
     }
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         self.with_child(ctx, |chld, ctx| chld.mouse_down(ctx, event));
@@ -99,6 +234,9 @@ impl UiWidget for WidgetHost {
     fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         self.with_child(ctx, |chld, ctx| chld.scroll(ctx, event));
     }
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        self.with_child(ctx, |chld, ctx| chld.mouse_leave(ctx));
+    }
     fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
         if self.state.keyboard_focus || self.state.child_keyboard_focus {
             self.with_child(ctx, |chld, ctx| chld.key_down(ctx, event));
@@ -112,7 +250,20 @@ impl UiWidget for WidgetHost {
     }
 
     fn timer(&mut self, ctx: &mut EventCtx, token: TimerToken) {
-        self.with_child(ctx, |chld, ctx| chld.timer(ctx, token));
+        // only the path to the widget that requested the timer
+        if self.state.timers.remove(&token) {
+            self.with_child(ctx, |chld, ctx| chld.timer(ctx, token));
+        }
+    }
+
+    // focus_changed is for the widget inside, through `set_focus`
+
+    fn for_each_host(
+        &mut self,
+        ctx: &mut EventCtx,
+        f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx),
+    ) {
+        f(self, ctx)
     }
 
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
@@ -121,8 +272,10 @@ impl UiWidget for WidgetHost {
             state: &mut self.state,
             window: ctx.window,
         };
-        // we always lay out eveything
-        self.child.layout(&mut child_ctx, bc)
+        // clean subtrees are skipped by the layout hosts
+        let size = self.child.layout(&mut child_ctx, bc);
+        self.state.needs_layout = false;
+        size
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx) {
@@ -130,15 +283,31 @@ impl UiWidget for WidgetHost {
             layout_state: ctx.layout_state,
             state: &self.state,
             render_ctx: ctx.render_ctx,
+            debug_paint: ctx.debug_paint,
+            region: ctx.region,
         };
-        // we always paint everything
-        self.child.paint(&mut child_ctx)
+        // the layout hosts skip what is outside the damaged region, which
+        // then still needs paint
+        if self.child.paint_damaged(&mut child_ctx) {
+            self.state.needs_paint = false;
+        }
+    }
+
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        self.child.inspect(nodes);
+        if let Some(node) = nodes.last_mut() {
+            node.mouse_focus = self.state.mouse_focus;
+            node.keyboard_focus = self.state.keyboard_focus;
+        }
     }
 }
 
 impl UiWidget for Box<dyn UiWidget> {
-    fn init(&mut self, ctx: &mut EventCtx) {
-        self.deref_mut().init(ctx)
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.deref_mut().mount(ctx)
+    }
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        self.deref_mut().unmount(ctx)
     }
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         self.deref_mut().mouse_down(ctx, event);
@@ -153,6 +322,12 @@ impl UiWidget for Box<dyn UiWidget> {
     fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         self.deref_mut().scroll(ctx, event);
     }
+    fn mouse_enter(&mut self, ctx: &mut EventCtx) {
+        self.deref_mut().mouse_enter(ctx);
+    }
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        self.deref_mut().mouse_leave(ctx);
+    }
     fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
         self.deref_mut().key_down(ctx, event);
     }
@@ -165,6 +340,18 @@ impl UiWidget for Box<dyn UiWidget> {
         self.deref_mut().timer(ctx, token);
     }
 
+    fn focus_changed(&mut self, ctx: &mut EventCtx, focused: bool) {
+        self.deref_mut().focus_changed(ctx, focused);
+    }
+
+    fn for_each_host(
+        &mut self,
+        ctx: &mut EventCtx,
+        f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx),
+    ) {
+        self.deref_mut().for_each_host(ctx, f);
+    }
+
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
         self.deref_mut().layout(ctx, bc)
     }
@@ -172,4 +359,12 @@ impl UiWidget for Box<dyn UiWidget> {
     fn paint(&mut self, ctx: &mut PaintCtx) {
         self.deref_mut().paint(ctx)
     }
+
+    fn type_name(&self) -> &'static str {
+        self.deref().type_name()
+    }
+
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        self.deref_mut().inspect(nodes)
+    }
 }
@@ -7,6 +7,10 @@ pub mod stack;
 pub mod button;
 pub mod background;
 pub mod map;
+pub mod keyed;
+pub mod align;
+pub mod sized_box;
+pub mod repaint_boundary;
 mod konst;
 
 pub use list::List;
@@ -17,3 +21,7 @@ pub use background::Background;
 pub use map::Map;
 pub use lazy::{Lazy, LazyData};
 pub use konst::Const;
+pub use keyed::{Key, Keyed};
+pub use align::Align;
+pub use sized_box::SizedBox;
+pub use repaint_boundary::RepaintBoundary;
@@ -0,0 +1,104 @@
+use super::layout::align as ui;
+use crate::core::*;
+use crate::kurbo::Rect;
+use crate::piet::UnitPoint;
+use crate::EventCtx;
+
+/// Aligns its child within the available space.
+pub struct Align<'a> {
+    align: UnitPoint,
+    width_factor: Option<f64>,
+    height_factor: Option<f64>,
+    inner: AnyView<'a>,
+}
+
+pub fn Align<'a>(inner: impl View<'a>) -> Align<'a> {
+    Align::new(inner)
+}
+
+impl<'a> Align<'a> {
+    /// Align the child to the top left.
+    pub fn new(inner: impl View<'a>) -> Self {
+        Align {
+            align: UnitPoint::TOP_LEFT,
+            width_factor: None,
+            height_factor: None,
+            inner: AnyView::new(inner),
+        }
+    }
+
+    /// Builder-style method for setting the alignment.
+    pub fn alignment(mut self, align: UnitPoint) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Center the child.
+    pub fn centered(mut self) -> Self {
+        self.align = UnitPoint::CENTER;
+        self
+    }
+
+    /// Align the child to the right.
+    pub fn right(mut self) -> Self {
+        self.align = UnitPoint::RIGHT;
+        self
+    }
+
+    /// Align the child to the left.
+    pub fn left(mut self) -> Self {
+        self.align = UnitPoint::LEFT;
+        self
+    }
+
+    /// Make the width a multiple of the child's width, instead of taking all
+    /// the available width.
+    pub fn width_factor(mut self, factor: f64) -> Self {
+        self.width_factor = Some(factor);
+        self
+    }
+
+    /// Make the height a multiple of the child's height, instead of taking
+    /// all the available height.
+    pub fn height_factor(mut self, factor: f64) -> Self {
+        self.height_factor = Some(factor);
+        self
+    }
+}
+
+pub struct AlignWidget {
+    ui: ui::Align,
+}
+
+impl<'a> View<'a> for Align<'a> {
+    type Widget = AlignWidget;
+
+    fn build(self) -> Self::Widget {
+        let mut ui = ui::Align::new(self.inner.build());
+        ui.align = self.align;
+        ui.width_factor = self.width_factor;
+        ui.height_factor = self.height_factor;
+        AlignWidget { ui }
+    }
+
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
+        // UnitPoint has no PartialEq, compare where it lands in a unit rect
+        let unit = Rect::new(0.0, 0.0, 1.0, 1.0);
+        let ui = &mut widget.ui;
+        if ui.align.resolve(unit) != self.align.resolve(unit)
+            || (ui.width_factor, ui.height_factor) != (self.width_factor, self.height_factor)
+        {
+            ui.align = self.align;
+            ui.width_factor = self.width_factor;
+            ui.height_factor = self.height_factor;
+            ctx.request_layout();
+        }
+        widget.ui.child.update(self.inner, ctx);
+    }
+}
+
+impl Widget for AlignWidget {
+    fn as_ui_widget(&mut self) -> &mut dyn crate::UiWidget {
+        &mut self.ui
+    }
+}
@@ -18,9 +18,11 @@ use super::layout::LayoutHost;
 use crate::core::{AnyView, View, Widget};
 use crate::kurbo::{Point, Size};
 use crate::piet::{Color, RenderContext};
+use crate::shell::Scale;
 use crate::widget::SingleChildContainer;
-use crate::{BoxConstraints, LayoutCtx, PaintCtx, UiWidget};
+use crate::{BoxConstraints, EventCtx, LayoutCtx, PaintCtx, UiWidget};
 
+#[derive(PartialEq)]
 struct BorderStyle {
     width: f64,
     color: Color,
@@ -82,11 +84,33 @@ impl<'a> View<'a> for Background<'a> {
         }
     }
 
-    fn update(self, widget: &mut Self::Widget) {
-        widget.background = self.background;
-        widget.border = self.border;
-        widget.corner_radius = self.corner_radius;
-        widget.inner.update(self.inner);
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
+        let border_width = |border: &Option<BorderStyle>| border.as_ref().map(|b| b.width);
+        if border_width(&widget.border) != border_width(&self.border) {
+            ctx.request_layout();
+        }
+        if widget.background != self.background
+            || widget.border != self.border
+            || widget.corner_radius != self.corner_radius
+        {
+            widget.background = self.background;
+            widget.border = self.border;
+            widget.corner_radius = self.corner_radius;
+            ctx.request_paint();
+        }
+        widget.inner.update(self.inner, ctx);
+    }
+}
+
+impl BackgroundWidget {
+    /// The border width, rounded to whole device pixels so it stays crisp.
+    fn border_width(&self, scale: Scale) -> f64 {
+        match &self.border {
+            Some(border) if border.width > 0.0 => {
+                (border.width * scale.x()).round().max(1.0) / scale.x()
+            }
+            _ => 0.0,
+        }
     }
 }
 
@@ -109,7 +133,7 @@ impl SingleChildContainer for BackgroundWidget {
 
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
         bc.debug_check("Container");
-        let border_width = self.border.as_ref().map(|b| b.width).unwrap_or(0.0);
+        let border_width = self.border_width(ctx.scale());
         let child_bc = bc.shrink((2.0 * border_width, 2.0 * border_width));
         let size = SingleChildContainer::layout(&mut self.inner, ctx, child_bc);
         let origin = Point::new(border_width, border_width);
@@ -128,12 +152,13 @@ impl SingleChildContainer for BackgroundWidget {
             ctx.fill(panel, color);
         }
 
+        let border_width = self.border_width(ctx.scale());
         if let Some(border) = &self.border {
             let border_rect = size
                 .to_rect()
-                .inset(border.width / -2.0)
+                .inset(border_width / -2.0)
                 .to_rounded_rect(self.corner_radius);
-            ctx.stroke(border_rect, &border.color, border.width);
+            ctx.stroke(border_rect, &border.color, border_width);
         };
 
         SingleChildContainer::paint(&mut self.inner, ctx);
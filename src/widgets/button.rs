@@ -1,5 +1,6 @@
 use crate::core::*;
 use crate::ui_widgets::button as ui;
+use crate::EventCtx;
 
 use std::any::Any;
 use std::borrow::Cow;
@@ -49,8 +50,8 @@ impl<'a> View<'a> for Button<'a> {
         }
     }
 
-    fn update(self, widget: &mut Self::Widget) {
-        widget.ui.text.update(AnyView::new(self.text));
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
+        widget.ui.text.update(AnyView::new(self.text), ctx);
         widget.ui.on_click = self.on_click;
     }
 }
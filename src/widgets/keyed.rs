@@ -0,0 +1,96 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+
+use crate::core::*;
+use crate::widget_host::WidgetHost;
+use crate::{EventCtx, UiWidget};
+
+/// Identity of a child across updates.
+///
+/// Children with the same key are matched up during reconciliation, no matter
+/// where they moved to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key(u64);
+
+impl Key {
+    pub fn new(key: impl Hash) -> Key {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        Key(hasher.finish())
+    }
+}
+
+/// A view tagged with a [`Key`], for use with [`List::keyed`].
+///
+/// [`List::keyed`]: super::List::keyed
+pub struct Keyed<V> {
+    pub(crate) key: Key,
+    pub(crate) view: V,
+}
+
+pub fn Keyed<K: Hash, V>(key: K, view: V) -> Keyed<V> {
+    Keyed {
+        key: Key::new(key),
+        view,
+    }
+}
+
+/// Reuse the widgets in `children` for `views`.
+///
+/// Keyed views get the widget that had the same key, unkeyed views get the
+/// unkeyed widgets in order. New widgets are mounted, and widgets that were
+/// not reused are unmounted and dropped. Layout is requested if the children
+/// changed.
+pub(crate) fn reconcile<'a>(
+    children: &mut Vec<WidgetHost>,
+    views: impl Iterator<Item = (Option<Key>, AnyView<'a>)>,
+    ctx: &mut EventCtx,
+) {
+    // remember where every child was, to notice when they move
+    let mut keyed: HashMap<Key, VecDeque<_>> = HashMap::new();
+    let mut unkeyed = VecDeque::new();
+    for (index, child) in children.drain(..).enumerate() {
+        match child.key {
+            Some(key) => {
+                let same_key = keyed.entry(key).or_default();
+                // children sharing a key are matched to its views in order
+                if !same_key.is_empty() {
+                    eprintln!("Duplicate key {:?} among children", key);
+                }
+                same_key.push_back((index, child));
+            }
+            None => unkeyed.push_back((index, child)),
+        }
+    }
+
+    let mut changed = false;
+    for (next_index, (key, view)) in views.enumerate() {
+        let existing = match key {
+            Some(key) => keyed.get_mut(&key).and_then(VecDeque::pop_front),
+            None => unkeyed.pop_front(),
+        };
+        let child = match existing {
+            Some((index, mut child)) => {
+                changed |= index != next_index;
+                child.update(view, ctx);
+                child
+            }
+            None => {
+                changed = true;
+                let mut child = WidgetHost::new(view.build()).with_key(key);
+                child.mount(ctx);
+                child
+            }
+        };
+        children.push(child);
+    }
+
+    for (_, mut child) in keyed.into_values().flatten().chain(unkeyed) {
+        changed = true;
+        child.unmount(ctx);
+    }
+    if changed {
+        ctx.request_layout();
+    }
+}
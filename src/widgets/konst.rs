@@ -1,4 +1,4 @@
-use crate::{core::*, vbox_dyn, view_bump::VBox, UiWidget};
+use crate::{core::*, vbox_dyn, view_bump::VBox, EventCtx, UiWidget};
 
 /// Does not propogate updates down the tree.
 pub struct Const<'a> {
@@ -26,7 +26,7 @@ impl<'a> View<'a> for Const<'a> {
         }
     }
 
-    fn update(self, _widget: &mut Self::Widget) {
+    fn update(self, _widget: &mut Self::Widget, _ctx: &mut EventCtx) {
         // no-op
     }
 }
@@ -15,26 +15,27 @@
 //! A widget that aligns its child (for example, centering it).
 
 use super::LayoutHost;
+use crate::core::AnyWidget;
 use crate::kurbo::{Rect, Size};
 use crate::piet::UnitPoint;
 use crate::widget::SingleChildContainer;
-use crate::{BoxConstraints, LayoutCtx, Widget};
+use crate::{BoxConstraints, LayoutCtx};
 
 /// A widget that aligns its child.
-pub struct Align<T> {
-    align: UnitPoint,
-    child: LayoutHost<T>,
-    width_factor: Option<f64>,
-    height_factor: Option<f64>,
+pub struct Align {
+    pub(crate) align: UnitPoint,
+    pub(crate) child: LayoutHost,
+    pub(crate) width_factor: Option<f64>,
+    pub(crate) height_factor: Option<f64>,
 }
 
-impl<T> Align<T> {
+impl Align {
     /// Create widget with alignment.
     ///
     /// Note that the `align` parameter is specified as a `UnitPoint` in
     /// terms of left and right. This is inadequate for bidi-aware layout
     /// and thus the API will change when druid gains bidi capability.
-    pub fn new(child: T) -> Align<T> {
+    pub fn new(child: AnyWidget) -> Align {
         Align {
             align: UnitPoint::TOP_LEFT,
             child: LayoutHost::new(child),
@@ -62,8 +63,8 @@ impl<T> Align<T> {
     }
 }
 
-impl<W: Widget> SingleChildContainer for Align<W> {
-    type Child = LayoutHost<W>;
+impl SingleChildContainer for Align {
+    type Child = LayoutHost;
 
     fn widget(&self) -> &Self::Child {
         &self.child
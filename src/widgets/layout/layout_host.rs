@@ -1,13 +1,19 @@
 use crate::core::{AnyView, AnyWidget};
+use crate::inspect::InspectNode;
 use crate::kurbo::{Affine, Point, Rect, Size};
 use crate::piet::RenderContext;
+use crate::shell::{KeyEvent, Scale, TimerToken};
 use crate::widget::SingleChildContainer;
+use crate::widget_host::WidgetHost;
 use crate::{BoxConstraints, EventCtx, LayoutCtx, MouseEvent, PaintCtx, UiWidget};
 
 /// Manages the position of a child widget.
 pub struct LayoutHost {
     state: LayoutState,
     debug_needs_set_origin: bool,
+    /// The size the child asked for, before it was made to fit the
+    /// constraints.
+    debug_child_size: Size,
     pub(crate) child: AnyWidget,
 }
 
@@ -17,6 +23,13 @@ pub(crate) struct LayoutState {
     pub(crate) size: Size,
     pub(crate) origin: Point,
     pub(crate) hovered: bool,
+    /// The origin in window coordinates, as of the last paint.
+    pub(crate) window_origin: Point,
+    /// The constraints of the last layout.
+    pub(crate) constraints: Option<BoxConstraints>,
+    /// The scale factor of the last layout. The window's root state holds
+    /// the current one.
+    pub(crate) scale: Scale,
 }
 
 impl LayoutHost {
@@ -29,11 +42,13 @@ impl LayoutHost {
             child,
             state: LayoutState::default(),
             debug_needs_set_origin: true,
+            debug_child_size: Size::ZERO,
         }
     }
 
-    pub fn update<'b>(&mut self, view: AnyView<'b>) {
-        view.update(&mut self.child);
+    /// Update the child, see [`AnyView::update`].
+    pub fn update<'b>(&mut self, view: AnyView<'b>, ctx: &mut EventCtx) {
+        self.with_child_ctx(ctx, |child, ctx| view.update(child, ctx));
     }
 
     /// Set the position of the child, relative to the origin of the parent.
@@ -47,10 +62,67 @@ impl LayoutHost {
         self.state.size
     }
 
+    /// Paint the child, unless it is outside the damaged region. Returns
+    /// whether it was painted.
+    pub(crate) fn paint_damaged(&mut self, ctx: &mut PaintCtx) -> bool {
+        // the debug overlay shows this in red instead
+        if self.debug_needs_set_origin && ctx.debug_paint.is_none() {
+            panic!("Missing call to set_origin");
+        }
+        // snap to device pixels, so edges stay crisp
+        let parent_origin = ctx.layout_state.window_origin;
+        let scale = ctx.layout_state.scale;
+        let window_origin = parent_origin + self.state.origin.to_vec2();
+        self.state.window_origin = Point::new(
+            round_to_px(window_origin.x, scale.x()),
+            round_to_px(window_origin.y, scale.y()),
+        );
+        let window_rect = Rect::from_origin_size(self.state.window_origin, self.state.size);
+        if !ctx.region.intersects(window_rect) {
+            return false;
+        }
+        let mut child_ctx = PaintCtx {
+            render_ctx: ctx.render_ctx,
+            state: ctx.state,
+            layout_state: &self.state,
+            debug_paint: ctx.debug_paint,
+            region: ctx.region,
+        };
+        child_ctx.with_save(|ctx| {
+            let layout_origin = ctx.layout_state.window_origin - parent_origin;
+            ctx.transform(Affine::translate(layout_origin));
+            self.child.paint(ctx);
+        });
+        if let Some(debug) = ctx.debug_paint {
+            let fits = match self.state.constraints {
+                Some(bc) => bc.is_valid() && bc.contains(self.debug_child_size),
+                None => false,
+            };
+            let rect = Rect::from_origin_size(self.state.origin, self.state.size);
+            debug.paint_bounds(ctx.render_ctx, rect, self.debug_needs_set_origin || !fits);
+        }
+        true
+    }
+
     fn contains(&self, mouse: &MouseEvent) -> bool {
         Rect::from_origin_size(self.state.origin, self.state.size).contains(mouse.pos)
     }
 
+    /// Call `f` with a context in the child's coordinates.
+    fn with_child_ctx<R>(
+        &mut self,
+        ctx: &mut EventCtx,
+        f: impl FnOnce(&mut AnyWidget, &mut EventCtx) -> R,
+    ) -> R {
+        let mut child_ctx = EventCtx {
+            state: ctx.state,
+            layout_state: &self.state,
+            window: ctx.window,
+            messages: ctx.messages,
+        };
+        f(&mut self.child, &mut child_ctx)
+    }
+
     fn propagate_mouse_if_needed(
         &mut self,
         ctx: &mut EventCtx,
@@ -67,7 +139,12 @@ impl LayoutHost {
             window: ctx.window,
             messages: ctx.messages,
         };
-        if was_hovered || ctx.layout_state.hovered || child_ctx.state.has_mouse_focus() {
+        match (was_hovered, child_ctx.layout_state.hovered) {
+            (false, true) => self.child.mouse_enter(&mut child_ctx),
+            (true, false) => self.child.mouse_leave(&mut child_ctx),
+            _ => {}
+        }
+        if was_hovered || child_ctx.layout_state.hovered || child_ctx.state.has_mouse_focus() {
             f(&mut self.child, &mut child_ctx, &mouse);
         }
     }
@@ -83,6 +160,18 @@ impl SingleChildContainer for LayoutHost {
     fn widget_mut(&mut self) -> &mut Self::Child {
         &mut self.child
     }
+
+    // everything the child gets is in its own frame, so that paint requests
+    // and `hovered` are about the child
+
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.with_child_ctx(ctx, |child, ctx| child.mount(ctx));
+    }
+
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        self.with_child_ctx(ctx, |child, ctx| child.unmount(ctx));
+    }
+
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         self.propagate_mouse_if_needed(ctx, event, |child, ctx, e| child.mouse_down(ctx, e));
     }
@@ -95,31 +184,99 @@ impl SingleChildContainer for LayoutHost {
         self.propagate_mouse_if_needed(ctx, event, |child, ctx, e| child.mouse_up(ctx, e));
     }
 
+    // the layout host sends this itself, when the mouse crosses its frame
+    fn mouse_enter(&mut self, _ctx: &mut EventCtx) {}
+
+    /// The mouse left an ancestor, and so this frame too.
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        if self.state.hovered {
+            self.state.hovered = false;
+            self.with_child_ctx(ctx, |child, ctx| child.mouse_leave(ctx));
+        }
+    }
+
+    fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
+        // wheel events go to whatever is under the pointer
+        if self.contains(event) {
+            let mut mouse = event.clone();
+            mouse.pos -= self.state.origin.to_vec2();
+            self.with_child_ctx(ctx, |child, ctx| child.scroll(ctx, &mouse));
+        }
+    }
+
+    fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
+        self.with_child_ctx(ctx, |child, ctx| child.key_down(ctx, event));
+    }
+
+    fn key_up(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
+        self.with_child_ctx(ctx, |child, ctx| child.key_up(ctx, event));
+    }
+
+    fn timer(&mut self, ctx: &mut EventCtx, token: TimerToken) {
+        self.with_child_ctx(ctx, |child, ctx| child.timer(ctx, token));
+    }
+
+    fn focus_changed(&mut self, ctx: &mut EventCtx, focused: bool) {
+        self.with_child_ctx(ctx, |child, ctx| child.focus_changed(ctx, focused));
+    }
+
+    fn for_each_host(
+        &mut self,
+        ctx: &mut EventCtx,
+        f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx),
+    ) {
+        self.with_child_ctx(ctx, |child, ctx| child.for_each_host(ctx, f));
+    }
+
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
         self.debug_needs_set_origin = true;
+        let scale = ctx.layout_state.scale;
+        if !ctx.state.needs_layout
+            && self.state.constraints == Some(bc)
+            && self.state.scale == scale
+        {
+            return self.state.size;
+        }
+        self.state.constraints = Some(bc);
+        self.state.scale = scale;
         let mut child_ctx = LayoutCtx {
             layout_state: &mut self.state,
             state: ctx.state,
             window: ctx.window,
         };
-        self.state.size = self.child.layout(&mut child_ctx, bc);
+        let size = self.child.layout(&mut child_ctx, bc);
+        self.debug_child_size = size;
+        // whole device pixels, as far as the constraints allow
+        let size = Size::new(
+            round_to_px(size.width, scale.x()),
+            round_to_px(size.height, scale.y()),
+        );
+        self.state.size = bc.constrain(size);
         //TODO: validate that size matches constraints?
         self.state.size
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx) {
-        if self.debug_needs_set_origin {
-            panic!("Missing call to set_origin");
-        }
-        let mut child_ctx = PaintCtx {
-            render_ctx: ctx.render_ctx,
-            state: ctx.state,
-            layout_state: &self.state,
-        };
-        child_ctx.with_save(|ctx| {
-            let layout_origin = ctx.layout_state.origin.to_vec2();
-            ctx.transform(Affine::translate(layout_origin));
-            self.child.paint(ctx);
+        self.paint_damaged(ctx);
+    }
+
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        let mut children = Vec::new();
+        self.child.inspect(&mut children);
+        nodes.push(InspectNode {
+            type_name: self.child.as_ui_widget().type_name(),
+            origin: self.state.origin,
+            size: self.state.size,
+            constraints: self.state.constraints,
+            hovered: self.state.hovered,
+            mouse_focus: false,
+            keyboard_focus: false,
+            children,
         });
     }
 }
+
+/// Round a length in display points to whole device pixels.
+fn round_to_px(value: f64, scale: f64) -> f64 {
+    (value * scale).round() / scale
+}
@@ -1,9 +1,9 @@
-// mod align;
+pub(crate) mod align;
 mod layout_host;
 mod sized_box;
 pub(crate) mod stack;
 
-// pub use align::Align;
+pub use align::Align;
 pub use layout_host::LayoutHost;
 pub use sized_box::SizedBox;
 
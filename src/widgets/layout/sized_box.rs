@@ -4,9 +4,9 @@ use crate::{BoxConstraints, LayoutCtx, UiWidget};
 
 #[derive(Debug, Default)]
 pub struct SizedBox<W> {
-    inner: W,
-    width: Option<f64>,
-    height: Option<f64>,
+    pub(crate) inner: W,
+    pub(crate) width: Option<f64>,
+    pub(crate) height: Option<f64>,
 }
 
 impl SizedBox<()> {
@@ -14,6 +14,7 @@
 
 //! A widget that arranges its children in a one-dimensional array.
 
+use crate::inspect::InspectNode;
 use crate::kurbo::{common::FloatExt, Point, Rect, Size};
 use crate::widget_host::WidgetHost;
 use crate::{BoxConstraints, EventCtx, LayoutCtx, MouseEvent, PaintCtx, UiWidget};
@@ -119,18 +120,32 @@ impl Axis for Vertical {
 }
 
 impl UiWidget for Stack {
-    fn init(&mut self, ctx: &mut EventCtx) {
-        self.children.iter_mut().for_each(|chld| chld.init(ctx))
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.children.iter_mut().for_each(|chld| chld.mount(ctx))
+    }
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        self.children.iter_mut().for_each(|chld| chld.unmount(ctx))
     }
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
-        self.children
-            .iter_mut()
-            .for_each(|chld| chld.mouse_down(ctx, event))
+        for chld in &mut self.children {
+            chld.mouse_down(ctx, event);
+            if ctx.is_handled() {
+                break;
+            }
+        }
     }
     fn mouse_up(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
+        for chld in &mut self.children {
+            chld.mouse_up(ctx, event);
+            if ctx.is_handled() {
+                break;
+            }
+        }
+    }
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
         self.children
             .iter_mut()
-            .for_each(|chld| chld.mouse_up(ctx, event))
+            .for_each(|chld| chld.mouse_leave(ctx))
     }
     fn mouse_move(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
         self.children
@@ -138,25 +153,41 @@ impl UiWidget for Stack {
             .for_each(|chld| chld.mouse_move(ctx, event))
     }
     fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
-        self.children
-            .iter_mut()
-            .for_each(|chld| chld.scroll(ctx, event))
+        for chld in &mut self.children {
+            chld.scroll(ctx, event);
+            if ctx.is_handled() {
+                break;
+            }
+        }
     }
     fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
-        self.children
-            .iter_mut()
-            .for_each(|chld| chld.key_down(ctx, event))
+        for chld in &mut self.children {
+            chld.key_down(ctx, event);
+            if ctx.is_handled() {
+                break;
+            }
+        }
     }
     fn key_up(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
-        self.children
-            .iter_mut()
-            .for_each(|chld| chld.key_up(ctx, event))
+        for chld in &mut self.children {
+            chld.key_up(ctx, event);
+            if ctx.is_handled() {
+                break;
+            }
+        }
     }
     fn timer(&mut self, ctx: &mut EventCtx, token: TimerToken) {
         self.children
             .iter_mut()
             .for_each(|chld| chld.timer(ctx, token))
     }
+    fn for_each_host(
+        &mut self,
+        ctx: &mut EventCtx,
+        f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx),
+    ) {
+        self.children.iter_mut().for_each(|chld| f(chld, ctx))
+    }
 
     fn paint(&mut self, ctx: &mut PaintCtx) {
         self.children.iter_mut().for_each(|chld| chld.paint(ctx))
@@ -186,4 +217,10 @@ impl UiWidget for Stack {
 
         my_size
     }
+
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        for child in &mut self.children {
+            child.inspect(nodes);
+        }
+    }
 }
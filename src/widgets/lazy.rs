@@ -1,5 +1,5 @@
 use crate::view_bump::VBox;
-use crate::{core::*, vbox_dyn, UiWidget};
+use crate::{core::*, vbox_dyn, EventCtx, UiWidget};
 
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -88,12 +88,14 @@ impl<'a> View<'a> for Lazy<'a> {
         }
     }
 
-    fn update(self, widget: &mut Self::Widget) {
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
         if widget.id != self.id || widget.version != self.version {
+            widget.inner.unmount(ctx);
             widget.inner = (self.builder)().build();
+            widget.inner.mount(ctx);
+            ctx.request_layout();
             widget.id = self.id;
             widget.version = self.version;
-            widget.inner.update((self.builder)());
         }
     }
 }
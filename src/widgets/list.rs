@@ -1,9 +1,10 @@
-use crate::{core::*, vbox_dyn, view_bump::VBox, widget_host::WidgetHost};
+use crate::{core::*, vbox_dyn, view_bump::VBox, widget_host::WidgetHost, EventCtx};
 
+use super::keyed::{self, Key, Keyed};
 use super::layout::stack::{Stack, Vertical};
 
 pub struct List<'a> {
-    iter: VBox<'a, dyn Iterator<Item = AnyView<'a>> + 'a>,
+    iter: VBox<'a, dyn Iterator<Item = (Option<Key>, AnyView<'a>)> + 'a>,
 }
 
 impl<'a> List<'a> {
@@ -12,9 +13,23 @@ impl<'a> List<'a> {
         I: IntoIterator + 'a,
         I::Item: View<'a>,
     {
-        let children = children.into_iter().map(AnyView::new);
+        let children = children.into_iter().map(|v| (None, AnyView::new(v)));
         List {
-            iter: vbox_dyn!(children, dyn Iterator<Item = AnyView<'a>> + 'a),
+            iter: vbox_dyn!(children, dyn Iterator<Item = (Option<Key>, AnyView<'a>)> + 'a),
+        }
+    }
+
+    /// A list whose rows are matched by key instead of by position.
+    pub fn keyed<I, V>(children: I) -> Self
+    where
+        I: IntoIterator<Item = Keyed<V>> + 'a,
+        V: View<'a>,
+    {
+        let children = children
+            .into_iter()
+            .map(|k| (Some(k.key), AnyView::new(k.view)));
+        List {
+            iter: vbox_dyn!(children, dyn Iterator<Item = (Option<Key>, AnyView<'a>)> + 'a),
         }
     }
 }
@@ -36,7 +51,7 @@ impl<'a> View<'a> for List<'a> {
 
     fn build(mut self) -> Self::Widget {
         let items = (&mut *self.iter)
-            .map(|w| WidgetHost::new(w.build()))
+            .map(|(key, w)| WidgetHost::new(w.build()).with_key(key))
             .collect();
 
         ListWidget {
@@ -47,17 +62,8 @@ impl<'a> View<'a> for List<'a> {
         }
     }
 
-    fn update(mut self, widget: &mut Self::Widget) {
-        let mut idx = 0;
-        for view in &mut *self.iter {
-            if idx < widget.ui.children.len() {
-                widget.ui.children[idx].update(view);
-            } else {
-                widget.ui.children.push(WidgetHost::new(view.build()));
-            }
-            idx += 1;
-        }
-        widget.ui.children.truncate(idx);
+    fn update(mut self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
+        keyed::reconcile(&mut widget.ui.children, &mut *self.iter, ctx);
     }
 }
 
@@ -2,24 +2,61 @@ use std::any::Any;
 
 use druid_shell::{KeyEvent, TimerToken};
 
+use crate::widget_host::WidgetHost;
 use crate::{core::*, widget::SingleChildContainer, EventCtx, MouseEvent, UiWidget};
 
+type MapFn = Box<dyn FnMut(Box<dyn Any>, &mut EventCtx) -> Option<Box<dyn Any>>>;
+
+/// Translates the messages emitted by its child.
+///
+/// Messages of type `T` are turned into `U`, or dropped, other messages pass
+/// through untouched. Messages emitted outside the child are never mapped.
 pub struct Map<'a> {
-    map: Box<dyn Fn(Box<dyn Any>) -> Box<dyn Any>>,
+    map: MapFn,
     inner: AnyView<'a>,
 }
 
 pub struct MapWidget {
-    map: Box<dyn Fn(Box<dyn Any>) -> Box<dyn Any>>,
+    map: MapFn,
     inner: AnyWidget,
 }
 
 impl<'a> Map<'a> {
-    pub fn new<T, U>(_map: fn(T) -> U, _inner: AnyView) -> Self {
-        todo!()
+    pub fn new<T, U>(mut map: impl FnMut(T) -> U + 'static, inner: impl View<'a>) -> Self
+    where
+        T: 'static,
+        U: 'static,
+    {
+        Map::filter_map(move |msg, _| Some(map(msg)), inner)
+    }
+
+    /// Map the messages of type `T`, dropping those `map` returns `None` for.
+    pub fn filter_map<T, U>(
+        mut map: impl FnMut(T, &mut EventCtx) -> Option<U> + 'static,
+        inner: impl View<'a>,
+    ) -> Self
+    where
+        T: 'static,
+        U: 'static,
+    {
+        Map {
+            map: Box::new(move |msg, ctx| match msg.downcast::<T>() {
+                Ok(msg) => map(*msg, ctx).map(|msg| Box::new(msg) as Box<dyn Any>),
+                Err(msg) => Some(msg),
+            }),
+            inner: AnyView::new(inner),
+        }
     }
 }
 
+pub fn Map<'a, T, U>(map: impl FnMut(T) -> U + 'static, inner: impl View<'a>) -> Map<'a>
+where
+    T: 'static,
+    U: 'static,
+{
+    Map::new(map, inner)
+}
+
 impl<'a> View<'a> for Map<'a> {
     type Widget = MapWidget;
     fn build(self) -> Self::Widget {
@@ -29,9 +66,10 @@ impl<'a> View<'a> for Map<'a> {
         }
     }
 
-    fn update(self, widget: &mut Self::Widget) {
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
         widget.map = self.map;
-        self.inner.update(&mut widget.inner);
+        let inner = self.inner;
+        widget.with_child(ctx, |chld, ctx| inner.update(chld, ctx));
     }
 }
 
@@ -42,13 +80,26 @@ impl Widget for MapWidget {
 }
 
 impl MapWidget {
-    fn check_msgs(&mut self, ctx: &mut EventCtx) {
-        take_mut::take(ctx.messages, |mut msgs| {
-            for msg in &mut msgs {
-                take_mut::take(msg, |msg| (self.map)(msg));
+    /// Run `f` with a message queue of its own, and map whatever ends up in it.
+    fn with_child<R>(
+        &mut self,
+        parent_ctx: &mut EventCtx,
+        f: impl FnOnce(&mut AnyWidget, &mut EventCtx) -> R,
+    ) -> R {
+        let mut messages = Vec::new();
+        let mut child_ctx = EventCtx {
+            window: parent_ctx.window,
+            state: parent_ctx.state,
+            layout_state: parent_ctx.layout_state,
+            messages: &mut messages,
+        };
+        let r = f(&mut self.inner, &mut child_ctx);
+        for msg in messages {
+            if let Some(msg) = (self.map)(msg, parent_ctx) {
+                parent_ctx.messages.push(msg);
             }
-            msgs
-        })
+        }
+        r
     }
 }
 
@@ -63,43 +114,59 @@ impl SingleChildContainer for MapWidget {
         &mut self.inner
     }
 
-    fn init(&mut self, ctx: &mut EventCtx) {
-        self.widget_mut().init(ctx);
-        self.check_msgs(ctx);
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.with_child(ctx, |chld, ctx| chld.mount(ctx));
+    }
+
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        self.with_child(ctx, |chld, ctx| chld.unmount(ctx));
     }
 
     fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
-        self.widget_mut().mouse_down(ctx, event);
-        self.check_msgs(ctx);
+        self.with_child(ctx, |chld, ctx| chld.mouse_down(ctx, event));
     }
 
     fn mouse_up(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
-        self.widget_mut().mouse_up(ctx, event);
-        self.check_msgs(ctx);
+        self.with_child(ctx, |chld, ctx| chld.mouse_up(ctx, event));
     }
 
     fn mouse_move(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
-        self.widget_mut().mouse_move(ctx, event);
-        self.check_msgs(ctx);
+        self.with_child(ctx, |chld, ctx| chld.mouse_move(ctx, event));
     }
 
     fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
-        self.widget_mut().scroll(ctx, event);
-        self.check_msgs(ctx);
+        self.with_child(ctx, |chld, ctx| chld.scroll(ctx, event));
+    }
+
+    fn mouse_enter(&mut self, ctx: &mut EventCtx) {
+        self.with_child(ctx, |chld, ctx| chld.mouse_enter(ctx));
+    }
+
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        self.with_child(ctx, |chld, ctx| chld.mouse_leave(ctx));
     }
 
     fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
-        self.widget_mut().key_down(ctx, event);
-        self.check_msgs(ctx);
+        self.with_child(ctx, |chld, ctx| chld.key_down(ctx, event));
     }
 
     fn key_up(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
-        self.widget_mut().key_up(ctx, event);
-        self.check_msgs(ctx);
+        self.with_child(ctx, |chld, ctx| chld.key_up(ctx, event));
     }
 
     fn timer(&mut self, ctx: &mut EventCtx, token: TimerToken) {
-        self.widget_mut().timer(ctx, token);
-        self.check_msgs(ctx);
+        self.with_child(ctx, |chld, ctx| chld.timer(ctx, token));
+    }
+
+    fn focus_changed(&mut self, ctx: &mut EventCtx, focused: bool) {
+        self.with_child(ctx, |chld, ctx| chld.focus_changed(ctx, focused));
+    }
+
+    fn for_each_host(
+        &mut self,
+        ctx: &mut EventCtx,
+        f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx),
+    ) {
+        self.with_child(ctx, |chld, ctx| chld.for_each_host(ctx, f));
     }
 }
@@ -0,0 +1,185 @@
+//! Caching the painted output of a subtree.
+
+use crate::core::{AnyView, View, Widget};
+use crate::inspect::InspectNode;
+use crate::kurbo::{Point, Rect, Size};
+use crate::piet::{Device, Error, ImageFormat, InterpolationMode, PietImage, RenderContext};
+use crate::shell::{Region, Scale};
+use crate::widget_host::WidgetHost;
+use crate::{BoxConstraints, EventCtx, LayoutCtx, MouseEvent, PaintCtx, UiWidget};
+use druid_shell::{KeyEvent, TimerToken};
+
+/// Paints its child into an offscreen image and reuses that image until the
+/// child requests paint or layout.
+///
+/// Useful for static panels that are expensive to paint, like large blocks of
+/// text. A [`Const`](super::Const) child is never painted again.
+pub struct RepaintBoundary<'a> {
+    inner: AnyView<'a>,
+}
+
+pub fn RepaintBoundary<'a>(inner: impl View<'a>) -> RepaintBoundary<'a> {
+    RepaintBoundary::new(inner)
+}
+
+impl<'a> RepaintBoundary<'a> {
+    pub fn new(inner: impl View<'a>) -> Self {
+        RepaintBoundary {
+            inner: AnyView::new(inner),
+        }
+    }
+}
+
+pub struct RepaintBoundaryWidget {
+    inner: WidgetHost,
+    /// The painted child, and the size and scale it was painted at.
+    cache: Option<(PietImage, Size, Scale)>,
+    /// Kept for the next repaint. The bitmap borrows it, so that one is made
+    /// again every time.
+    device: Option<Device>,
+}
+
+impl<'a> View<'a> for RepaintBoundary<'a> {
+    type Widget = RepaintBoundaryWidget;
+
+    fn build(self) -> Self::Widget {
+        RepaintBoundaryWidget {
+            inner: WidgetHost::new(self.inner.build()),
+            cache: None,
+            device: None,
+        }
+    }
+
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
+        // anything that changes the picture requests paint, which the cache
+        // picks up in `paint`
+        widget.inner.update(self.inner, ctx);
+    }
+}
+
+impl Widget for RepaintBoundaryWidget {
+    fn as_ui_widget(&mut self) -> &mut dyn UiWidget {
+        self
+    }
+}
+
+impl RepaintBoundaryWidget {
+    fn paint_offscreen(&mut self, ctx: &mut PaintCtx, size: Size) -> Result<PietImage, Error> {
+        let scale = ctx.scale();
+        let width = (size.width * scale.x()).ceil() as usize;
+        let height = (size.height * scale.y()).ceil() as usize;
+        // the whole child is damaged, in window coordinates
+        let region = Region::from(Rect::from_origin_size(ctx.layout_state.window_origin, size));
+
+        if self.device.is_none() {
+            self.device = Some(Device::new()?);
+        }
+        let device = self.device.as_mut().unwrap();
+        let mut target = device.bitmap_target(width, height, scale.x())?;
+        {
+            let mut piet = target.render_context();
+            let mut child_ctx = PaintCtx {
+                state: ctx.state,
+                layout_state: ctx.layout_state,
+                render_ctx: &mut piet,
+                debug_paint: None,
+                region: &region,
+            };
+            self.inner.paint(&mut child_ctx);
+            piet.finish()?;
+        }
+        let buf = target.to_image_buf(ImageFormat::RgbaPremul)?;
+        Ok(buf.to_image(ctx.render_ctx))
+    }
+}
+
+impl UiWidget for RepaintBoundaryWidget {
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.inner.mount(ctx)
+    }
+
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        self.inner.unmount(ctx)
+    }
+
+    fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
+        self.inner.mouse_down(ctx, event)
+    }
+
+    fn mouse_up(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
+        self.inner.mouse_up(ctx, event)
+    }
+
+    fn mouse_move(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
+        self.inner.mouse_move(ctx, event)
+    }
+
+    fn scroll(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
+        self.inner.scroll(ctx, event)
+    }
+
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        self.inner.mouse_leave(ctx)
+    }
+
+    fn key_down(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
+        self.inner.key_down(ctx, event)
+    }
+
+    fn key_up(&mut self, ctx: &mut EventCtx, event: &KeyEvent) {
+        self.inner.key_up(ctx, event)
+    }
+
+    fn timer(&mut self, ctx: &mut EventCtx, token: TimerToken) {
+        self.inner.timer(ctx, token)
+    }
+
+    fn for_each_host(
+        &mut self,
+        ctx: &mut EventCtx,
+        f: &mut dyn FnMut(&mut WidgetHost, &mut EventCtx),
+    ) {
+        f(&mut self.inner, ctx)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
+        let size = self.inner.layout(ctx, bc);
+        self.inner.set_origin(Point::ZERO);
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx) {
+        let size = self.inner.size();
+        // the debug overlay changes every frame, so it bypasses the cache
+        if ctx.debug_paint.is_some() || size.is_empty() {
+            self.cache = None;
+            self.inner.paint(ctx);
+            return;
+        }
+
+        let stale = match &self.cache {
+            Some((_, cached_size, cached_scale)) => {
+                *cached_size != size || *cached_scale != ctx.scale() || self.inner.needs_paint()
+            }
+            None => true,
+        };
+        if stale {
+            self.cache = match self.paint_offscreen(ctx, size) {
+                Ok(image) => Some((image, size, ctx.scale())),
+                Err(e) => {
+                    eprintln!("Failed to paint RepaintBoundary offscreen: '{}'", e);
+                    None
+                }
+            };
+        }
+
+        match &self.cache {
+            Some((image, _, _)) => ctx.draw_image(image, size.to_rect(), InterpolationMode::Bilinear),
+            None => self.inner.paint(ctx),
+        }
+    }
+
+    fn inspect(&mut self, nodes: &mut Vec<InspectNode>) {
+        self.inner.inspect(nodes)
+    }
+}
@@ -0,0 +1,62 @@
+use super::layout::SizedBox as ui;
+use crate::core::*;
+use crate::EventCtx;
+
+/// Gives its child a fixed width and/or height.
+pub struct SizedBox<'a> {
+    width: Option<f64>,
+    height: Option<f64>,
+    inner: AnyView<'a>,
+}
+
+impl<'a> SizedBox<'a> {
+    pub fn new(inner: impl View<'a>) -> Self {
+        SizedBox {
+            width: None,
+            height: None,
+            inner: AnyView::new(inner),
+        }
+    }
+
+    /// Set container's width.
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Set container's height.
+    pub fn height(mut self, height: f64) -> Self {
+        self.height = Some(height);
+        self
+    }
+}
+
+pub struct SizedBoxWidget {
+    ui: ui<AnyWidget>,
+}
+
+impl<'a> View<'a> for SizedBox<'a> {
+    type Widget = SizedBoxWidget;
+
+    fn build(self) -> Self::Widget {
+        let mut ui = ui::new(self.inner.build());
+        ui.width = self.width;
+        ui.height = self.height;
+        SizedBoxWidget { ui }
+    }
+
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
+        if (widget.ui.width, widget.ui.height) != (self.width, self.height) {
+            widget.ui.width = self.width;
+            widget.ui.height = self.height;
+            ctx.request_layout();
+        }
+        self.inner.update(&mut widget.ui.inner, ctx);
+    }
+}
+
+impl Widget for SizedBoxWidget {
+    fn as_ui_widget(&mut self) -> &mut dyn crate::UiWidget {
+        &mut self.ui
+    }
+}
@@ -1,7 +1,9 @@
+use super::keyed::{self, Key};
 use super::layout::stack as ui;
 use crate::core::*;
 use crate::view_bump::VVec;
 use crate::widget_host::WidgetHost;
+use crate::EventCtx;
 
 pub fn Column<'a>() -> Stack<'a> {
     Stack::column()
@@ -12,7 +14,7 @@ pub fn Row<'a>() -> Stack<'a> {
 }
 
 pub struct Stack<'a> {
-    children: VVec<'a, AnyView<'a>>,
+    children: VVec<'a, (Option<Key>, AnyView<'a>)>,
     axis: &'static dyn ui::Axis,
 }
 
@@ -35,7 +37,17 @@ impl<'a> Stack<'a> {
     where
         V: View<'a>,
     {
-        self.children.push(AnyView::new(widget));
+        self.children.push((None, AnyView::new(widget)));
+        self
+    }
+
+    /// Add a child that is matched by `key` instead of by position.
+    pub fn keyed_child<K, V>(mut self, key: K, widget: V) -> Self
+    where
+        K: std::hash::Hash,
+        V: View<'a>,
+    {
+        self.children.push((Some(Key::new(key)), AnyView::new(widget)));
         self
     }
 }
@@ -48,23 +60,20 @@ impl<'a> View<'a> for Stack<'a> {
                 children: self
                     .children
                     .into_iter()
-                    .map(|c| WidgetHost::new(c.build()))
+                    .map(|(key, c)| WidgetHost::new(c.build()).with_key(key))
                     .collect(),
                 axis: self.axis,
             },
         }
     }
 
-    fn update(self, widget: &mut Self::Widget) {
-        widget.ui.axis = self.axis;
-        widget.ui.children.truncate(self.children.len());
-        let mut views = self.children.into_iter();
-        for child in &mut widget.ui.children {
-            child.update(views.next().unwrap());
-        }
-        for child in views {
-            widget.ui.children.push(WidgetHost::new(child.build()));
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
+        // the axes are unit structs, tell them apart by what they do
+        if widget.ui.axis.pack(1.0, 0.0) != self.axis.pack(1.0, 0.0) {
+            widget.ui.axis = self.axis;
+            ctx.request_layout();
         }
+        keyed::reconcile(&mut widget.ui.children, self.children.into_iter(), ctx);
     }
 }
 
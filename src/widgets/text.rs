@@ -1,5 +1,5 @@
 use crate::ui_widgets::text as ui;
-use crate::{core::*, UiWidget};
+use crate::{core::*, EventCtx, UiWidget};
 
 use std::borrow::Cow;
 
@@ -30,9 +30,10 @@ impl<'a> View<'a> for Text<'a> {
         }
     }
 
-    fn update(self, widget: &mut Self::Widget) {
+    fn update(self, widget: &mut Self::Widget, ctx: &mut EventCtx) {
         if widget.ui.text() != self.text {
             widget.ui.set_text(self.text.into_owned());
+            ctx.request_layout();
         }
     }
 }
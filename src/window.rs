@@ -1,27 +1,47 @@
 use std::any::Any;
-use std::mem;
 
-use crate::app::AppHolder;
+use crate::backend::{DetachedWindow, WindowHost};
+use crate::core::{AnyView, AnyWidget};
+use crate::debug_paint::DebugPaint;
+use crate::inspect::InspectNode;
 use crate::kurbo::{Point, Size};
 use crate::piet::Piet;
 
-use crate::widget_host::{WidgetHost, WidgetState};
+use crate::widget_host::{FocusChange, WidgetHost, WidgetId, WidgetState};
+use crate::widgets::keyed::Key;
 use crate::widgets::layout::LayoutState;
 use crate::{BoxConstraints, EventCtx, LayoutCtx, PaintCtx, UiWidget};
-use druid_shell::{IdleToken, KeyEvent, MouseEvent, Region, TimerToken, WindowHandle};
+use druid_shell::{KbKey, KeyEvent, MouseEvent, Region, Scale, TimerToken};
+use std::rc::Rc;
+
+/// Identity of a window within the application.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WindowId {
+    /// The window showing [`Application::view`](crate::Application::view).
+    Main,
+    /// A window from [`Application::windows`](crate::Application::windows).
+    Keyed(Key),
+}
 
 pub struct Window {
-    handle: WindowHandle,
+    pub(crate) id: WindowId,
+    pub(crate) handle: Rc<dyn WindowHost>,
     root_state: WidgetState,
     layout_state: LayoutState,
-    app: AppHolder,
+    root: WidgetHost,
+    title: String,
+    /// Paint the layout bounds overlay.
+    debug_paint: bool,
+    frame: u64,
+    /// The widget with keyboard focus.
+    focus: Option<WidgetId>,
     pub(crate) messages: Vec<Box<dyn Any>>,
 }
 
 impl Window {
     fn with_event_ctx<R>(&mut self, f: impl FnOnce(&mut WidgetHost, &mut EventCtx) -> R) -> R {
         let mut ctx = EventCtx {
-            window: &self.handle,
+            window: &*self.handle,
             state: &mut self.root_state,
             layout_state: &self.layout_state,
             messages: &mut self.messages,
@@ -32,52 +52,142 @@ impl Window {
         //self.root_state.request_update = false;
         //}
 
-        self.app.with_host(|w| f(w, &mut ctx))
+        ctx.state.is_handled = false;
+        let r = f(&mut self.root, &mut ctx);
+        if let Some(change) = self.root_state.request_focus.take() {
+            self.change_focus(change);
+        }
+        // the root hosts route timers down, nothing above it looks them up
+        self.root_state.timers.clear();
+        self.root_state.dropped_timers.clear();
+        r
+    }
+
+    fn change_focus(&mut self, change: FocusChange) {
+        let mut ctx = EventCtx {
+            window: &*self.handle,
+            state: &mut self.root_state,
+            layout_state: &self.layout_state,
+            messages: &mut self.messages,
+        };
+        let focus = match change {
+            FocusChange::Focus(id) => Some(id),
+            FocusChange::Resign => None,
+            FocusChange::Next | FocusChange::Previous => {
+                let chain = self.root.focus_chain(&mut ctx);
+                let current = self
+                    .focus
+                    .and_then(|focus| chain.iter().position(|id| *id == focus));
+                let len = chain.len();
+                let next = match (change, current) {
+                    _ if len == 0 => None,
+                    (FocusChange::Next, Some(i)) => Some((i + 1) % len),
+                    (FocusChange::Next, None) => Some(0),
+                    (_, Some(i)) => Some((i + len - 1) % len),
+                    (_, None) => Some(len - 1),
+                };
+                next.map(|i| chain[i])
+            }
+        };
+        if focus != self.focus {
+            self.focus = focus;
+            self.root.set_focus(&mut ctx, focus);
+        }
+        // a widget can ask for focus when it gets or loses it
+        if let Some(change) = self.root_state.request_focus.take() {
+            self.change_focus(change);
+        }
     }
 
-    pub fn new(handle: WindowHandle, app: AppHolder) -> Self {
+    pub(crate) fn new(id: WindowId, root: AnyWidget, title: String) -> Self {
         Window {
-            handle,
-            app,
+            id,
+            // replaced once the platform window is connected
+            handle: Rc::new(DetachedWindow),
+            root: WidgetHost::new(root),
+            title,
+            debug_paint: false,
+            frame: 0,
+            focus: None,
             messages: Default::default(),
             layout_state: Default::default(),
             root_state: Default::default(),
         }
     }
 
-    pub fn update(&mut self) {
-        for msg in mem::take(&mut self.messages) {
-            self.app.update(msg);
+    pub fn handle(&self) -> &dyn WindowHost {
+        &*self.handle
+    }
+
+    pub fn root_size(&self) -> Size {
+        self.root.size()
+    }
+
+    pub fn update(&mut self, view: AnyView, title: Option<String>) {
+        self.with_event_ctx(|chld, ctx| chld.update(view, ctx));
+        if let Some(title) = title {
+            self.set_title(title);
         }
     }
 
-    pub fn window_connected(&mut self) {
-        self.with_event_ctx(|chld, ctx| chld.init(ctx));
+    fn set_title(&mut self, title: String) {
+        if title != self.title {
+            self.handle.set_title(&title);
+            self.title = title;
+        }
+    }
+
+    pub fn window_connected(&mut self, handle: Rc<dyn WindowHost>) {
+        self.handle = handle;
+        self.handle.set_title(&self.title);
+        self.with_event_ctx(|chld, ctx| chld.mount(ctx));
+    }
+
+    pub fn window_disconnected(&mut self) {
+        self.with_event_ctx(|chld, ctx| chld.unmount(ctx));
     }
 
     pub fn prepare_paint(&mut self) {
         let mut ctx = LayoutCtx {
             state: &self.root_state,
             layout_state: &self.layout_state,
-            window: &self.handle,
+            window: &*self.handle,
         };
         let bc = BoxConstraints::tight(self.layout_state.size);
-        self.app.with_host(|root| {
-            root.layout(&mut ctx, bc);
-            root.set_origin(Point::ZERO);
-        })
+        self.root.layout(&mut ctx, bc);
+        self.root.set_origin(Point::ZERO);
+        self.root_state.needs_layout = false;
     }
 
-    pub fn paint(&mut self, piet: &mut Piet, _region: &Region) {
+    pub fn paint(&mut self, piet: &mut Piet, region: &Region) {
+        self.frame += 1;
+        let debug_paint = if self.debug_paint {
+            Some(DebugPaint { frame: self.frame })
+        } else {
+            None
+        };
         let mut ctx = PaintCtx {
             state: &self.root_state,
             layout_state: &self.layout_state,
             render_ctx: piet,
+            debug_paint,
+            region,
         };
 
-        self.app.with_host(|root| {
-            root.paint(&mut ctx);
-        })
+        self.root.paint(&mut ctx);
+        self.root_state.needs_paint = false;
+
+        if let Some(debug) = debug_paint {
+            let tree = self.inspect();
+            debug.paint_overlay(piet, &tree, self.layout_state.size);
+        }
+    }
+
+    /// Outline every widget, with bad layouts in red, and label the hovered
+    /// widget. Toggled with F11 in debug builds.
+    pub fn set_debug_paint(&mut self, debug_paint: bool) {
+        self.debug_paint = debug_paint;
+        self.handle.invalidate();
     }
 
     pub fn size_changed(&mut self, new_size: Size) {
@@ -85,6 +195,14 @@ impl Window {
         self.handle.invalidate();
     }
 
+    /// Every widget is laid out again at the new scale.
+    pub fn scale_changed(&mut self, scale: Scale) {
+        if scale != self.layout_state.scale {
+            self.layout_state.scale = scale;
+            self.handle.invalidate();
+        }
+    }
+
     pub fn mouse_down(&mut self, event: &MouseEvent) {
         let event = event.to_owned().into();
         self.with_event_ctx(|chld, ctx| chld.mouse_down(ctx, &event))
@@ -98,7 +216,19 @@ impl Window {
     pub fn mouse_move(&mut self, event: &MouseEvent) {
         //eprintln!("window mouse move {}", event.pos);
         let event = event.to_owned().into();
-        self.with_event_ctx(|chld, ctx| chld.mouse_move(ctx, &event))
+        self.with_event_ctx(|chld, ctx| chld.mouse_move(ctx, &event));
+        if self.debug_paint {
+            // the overlay follows the hovered widget
+            self.handle.invalidate();
+        }
+    }
+
+    /// The mouse left the window, nothing is hovered anymore.
+    pub fn mouse_leave(&mut self) {
+        self.with_event_ctx(|chld, ctx| chld.mouse_leave(ctx));
+        if self.debug_paint {
+            self.handle.invalidate();
+        }
     }
 
     pub fn scroll(&mut self, event: &MouseEvent) {
@@ -106,8 +236,45 @@ impl Window {
         self.with_event_ctx(|chld, ctx| chld.scroll(ctx, &event))
     }
 
+    /// The widget tree of the window, as of the last layout.
+    pub fn inspect(&mut self) -> InspectNode {
+        let mut nodes = Vec::new();
+        self.root.inspect(&mut nodes);
+        nodes.pop().expect("root widget without a layout host")
+    }
+
     pub fn key_down(&mut self, event: KeyEvent) -> bool {
-        self.with_event_ctx(|chld, ctx| chld.key_down(ctx, &event));
+        if cfg!(debug_assertions) && event.key == KbKey::F11 {
+            self.set_debug_paint(!self.debug_paint);
+            return true;
+        }
+        // in debug builds, F12 dumps the widget tree, as json with shift
+        if cfg!(debug_assertions) && event.key == KbKey::F12 {
+            let tree = self.inspect();
+            if event.mods.shift() {
+                eprintln!("{}", tree.to_json());
+            } else {
+                eprint!("{}", tree.to_text());
+            }
+            return true;
+        }
+        let handled = self.with_event_ctx(|chld, ctx| {
+            chld.key_down(ctx, &event);
+            ctx.is_handled()
+        });
+        if handled {
+            return true;
+        }
+        // focus traversal is for keys no widget wanted
+        if event.key == KbKey::Tab {
+            let change = if event.mods.shift() {
+                FocusChange::Previous
+            } else {
+                FocusChange::Next
+            };
+            self.change_focus(change);
+            return true;
+        }
         false
     }
 
@@ -118,8 +285,4 @@ impl Window {
     pub fn timer(&mut self, token: TimerToken) {
         self.with_event_ctx(|chld, ctx| chld.timer(ctx, token))
     }
-
-    pub fn idle(&mut self, _token: IdleToken) {
-        //self.with_event_ctx(|chld, ctx| chld.id(ctx, token))
-    }
 }
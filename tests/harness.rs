@@ -0,0 +1,812 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use olma::core::{AnyView, AnyWidget, View, Widget};
+use olma::kurbo::{Rect, Size};
+use olma::shell::{KbKey, KeyEvent, MouseButton, Scale, TimerToken};
+use olma::widgets::*;
+use olma::{
+    Application, BoxConstraints, Command, EventCtx, ExtEventSink, Harness, LayoutCtx, MouseEvent,
+    PaintCtx, UiWidget, ViewExt,
+};
+
+struct Counter {
+    count: i32,
+}
+
+enum Msg {
+    Increment,
+}
+
+impl Application for Counter {
+    type Msg = Msg;
+
+    fn update(&mut self, msg: Msg) -> Command<Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+        }
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        Button::new("+").click(|| Msg::Increment).any()
+    }
+}
+
+#[test]
+fn layout_fills_window() {
+    let mut harness = Harness::new(Counter { count: 0 });
+    harness.layout((200., 100.));
+    assert_eq!(harness.root_size(), (200., 100.).into());
+}
+
+#[test]
+fn click_updates_model() {
+    let mut harness = Harness::new(Counter { count: 0 });
+    harness.layout((200., 100.));
+    harness.click((100., 50.));
+    harness.click((100., 50.));
+    assert_eq!(harness.app().count, 2);
+}
+
+#[test]
+fn inspect_reports_layout() {
+    let mut harness = Harness::new(Counter { count: 0 });
+    harness.layout((200., 100.));
+    let root = harness.inspect();
+    assert_eq!(root.size, (200., 100.).into());
+    assert!(root.type_name.ends_with("Button"));
+    assert_eq!(root.children.len(), 1);
+    assert!(harness.inspect().to_json().starts_with("{\"type\":"));
+}
+
+/// Requests a timer when clicked and reports the timers, scrolls, moves and
+/// leaves it receives.
+struct Probe(usize);
+
+struct ProbeWidget(usize);
+
+enum ProbeMsg {
+    Requested(TimerToken),
+    /// The probe, and whether it was hovered.
+    Fired(usize, bool),
+    Scrolled(usize),
+    Moved(usize),
+    Left(usize),
+}
+
+impl<'a> View<'a> for Probe {
+    type Widget = ProbeWidget;
+
+    fn build(self) -> ProbeWidget {
+        ProbeWidget(self.0)
+    }
+
+    fn update(self, _widget: &mut ProbeWidget, _ctx: &mut EventCtx) {}
+}
+
+impl Widget for ProbeWidget {
+    fn as_ui_widget(&mut self) -> &mut dyn UiWidget {
+        self
+    }
+}
+
+impl UiWidget for ProbeWidget {
+    fn mouse_down(&mut self, ctx: &mut EventCtx, _event: &MouseEvent) {
+        let token = ctx.request_timer(Duration::from_millis(10));
+        ctx.submit_message(Box::new(ProbeMsg::Requested(token)));
+    }
+
+    fn timer(&mut self, ctx: &mut EventCtx, _token: TimerToken) {
+        let hovered = ctx.hovered();
+        ctx.submit_message(Box::new(ProbeMsg::Fired(self.0, hovered)));
+    }
+
+    fn scroll(&mut self, ctx: &mut EventCtx, _event: &MouseEvent) {
+        ctx.submit_message(Box::new(ProbeMsg::Scrolled(self.0)));
+    }
+
+    fn mouse_move(&mut self, ctx: &mut EventCtx, _event: &MouseEvent) {
+        ctx.submit_message(Box::new(ProbeMsg::Moved(self.0)));
+    }
+
+    fn mouse_leave(&mut self, ctx: &mut EventCtx) {
+        ctx.submit_message(Box::new(ProbeMsg::Left(self.0)));
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
+        bc.constrain((100., 50.))
+    }
+}
+
+#[derive(Default)]
+struct Probes {
+    token: Option<TimerToken>,
+    fired: Vec<(usize, bool)>,
+    scrolled: Vec<usize>,
+    moved: Vec<usize>,
+    left: Vec<usize>,
+}
+
+impl Application for Probes {
+    type Msg = ProbeMsg;
+
+    fn update(&mut self, msg: ProbeMsg) -> Command<ProbeMsg> {
+        match msg {
+            ProbeMsg::Requested(token) => self.token = Some(token),
+            ProbeMsg::Fired(probe, hovered) => self.fired.push((probe, hovered)),
+            ProbeMsg::Scrolled(probe) => self.scrolled.push(probe),
+            ProbeMsg::Moved(probe) => self.moved.push(probe),
+            ProbeMsg::Left(probe) => self.left.push(probe),
+        }
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        Column().child(Probe(0)).child(Probe(1)).any()
+    }
+}
+
+#[test]
+fn timer_reaches_only_its_owner() {
+    let mut harness = Harness::new(Probes::default());
+    harness.layout((200., 100.));
+    harness.click((50., 75.));
+    let token = harness.app().token.expect("probe didn't request a timer");
+    harness.timer(token);
+    assert_eq!(harness.app().fired, vec![(1, true)]);
+    // a token nobody asked for goes nowhere
+    harness.timer(TimerToken::next());
+    assert_eq!(harness.app().fired, vec![(1, true)]);
+}
+
+#[test]
+fn timer_sees_its_own_frame() {
+    let mut harness = Harness::new(Probes::default());
+    harness.layout((200., 100.));
+    harness.click((50., 75.));
+    harness.timer(harness.app().token.unwrap());
+    harness.click((50., 75.));
+    harness.mouse_move((50., 25.));
+    harness.timer(harness.app().token.unwrap());
+    assert_eq!(harness.app().fired, vec![(1, true), (1, false)]);
+}
+
+#[test]
+fn scroll_reaches_the_widget_under_the_mouse() {
+    let mut harness = Harness::new(Probes::default());
+    harness.layout((200., 100.));
+    harness.scroll((50., 75.), (0., 10.));
+    assert_eq!(harness.app().scrolled, vec![1]);
+    harness.scroll((50., 25.), (0., 10.));
+    assert_eq!(harness.app().scrolled, vec![1, 0]);
+}
+
+fn key(key: KbKey) -> KeyEvent {
+    KeyEvent {
+        key,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn tab_focuses_button() {
+    let mut harness = Harness::new(Counter { count: 0 });
+    harness.layout((200., 100.));
+    assert!(!harness.inspect().keyboard_focus);
+    harness.key_down(key(KbKey::Tab));
+    assert!(harness.inspect().keyboard_focus);
+    assert!(harness.key_down(key(KbKey::Enter)));
+    assert_eq!(harness.app().count, 1);
+    // nothing wants escape, so it goes back to the platform
+    assert!(!harness.key_down(key(KbKey::Escape)));
+}
+
+#[test]
+fn leaving_window_clears_hover() {
+    let mut harness = Harness::new(Counter { count: 0 });
+    harness.layout((200., 100.));
+    harness.mouse_move((100., 50.));
+    assert!(harness.inspect().hovered);
+    harness.mouse_leave();
+    assert!(!harness.inspect().hovered);
+}
+
+/// A row that reports being mounted and unmounted. Clicking it inserts a new
+/// row in front of all rows, right clicking removes it.
+struct Row(usize);
+
+struct RowWidget(usize);
+
+#[derive(Debug, PartialEq)]
+enum RowMsg {
+    Mounted(usize),
+    Unmounted(usize),
+    Insert,
+    Remove(usize),
+}
+
+impl<'a> View<'a> for Row {
+    type Widget = RowWidget;
+
+    fn build(self) -> RowWidget {
+        RowWidget(self.0)
+    }
+
+    fn update(self, _widget: &mut RowWidget, _ctx: &mut EventCtx) {}
+}
+
+impl Widget for RowWidget {
+    fn as_ui_widget(&mut self) -> &mut dyn UiWidget {
+        self
+    }
+}
+
+impl UiWidget for RowWidget {
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        ctx.submit_message(Box::new(RowMsg::Mounted(self.0)));
+    }
+
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        ctx.submit_message(Box::new(RowMsg::Unmounted(self.0)));
+    }
+
+    fn mouse_down(&mut self, ctx: &mut EventCtx, event: &MouseEvent) {
+        if !ctx.hovered() {
+            return;
+        }
+        let msg = match event.button {
+            MouseButton::Right => RowMsg::Remove(self.0),
+            _ => RowMsg::Insert,
+        };
+        ctx.submit_message(Box::new(msg));
+    }
+
+    fn layout(&mut self, _ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
+        bc.constrain((100., 50.))
+    }
+}
+
+struct Rows {
+    rows: Vec<usize>,
+    log: Vec<RowMsg>,
+    shared_key: bool,
+}
+
+impl Rows {
+    fn new() -> Self {
+        Rows {
+            rows: vec![0],
+            log: Vec::new(),
+            shared_key: false,
+        }
+    }
+
+    /// Rows that all have the same key.
+    fn sharing_key(rows: Vec<usize>) -> Self {
+        Rows {
+            rows,
+            log: Vec::new(),
+            shared_key: true,
+        }
+    }
+}
+
+impl Application for Rows {
+    type Msg = RowMsg;
+
+    fn update(&mut self, msg: RowMsg) -> Command<RowMsg> {
+        match msg {
+            RowMsg::Insert => self.rows.insert(0, self.rows.len()),
+            RowMsg::Remove(row) => self.rows.retain(|&r| r != row),
+            msg => self.log.push(msg),
+        }
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        let shared_key = self.shared_key;
+        let key = move |row| if shared_key { 0 } else { row };
+        List::keyed(self.rows.iter().map(move |&row| Keyed(key(row), Row(row)))).any()
+    }
+}
+
+#[test]
+fn keyed_rows_survive_insert_at_front() {
+    let mut harness = Harness::new(Rows::new());
+    harness.layout((200., 200.));
+    assert_eq!(harness.app().log, vec![RowMsg::Mounted(0)]);
+    harness.click((50., 25.));
+    harness.click((50., 25.));
+    assert_eq!(harness.app().rows, vec![2, 1, 0]);
+    // only the new rows were built, the old ones moved down
+    assert_eq!(
+        harness.app().log,
+        vec![RowMsg::Mounted(0), RowMsg::Mounted(1), RowMsg::Mounted(2)]
+    );
+}
+
+#[test]
+fn rows_are_mounted_and_unmounted() {
+    let mut harness = Harness::new(Rows::new());
+    harness.layout((200., 200.));
+    harness.click((50., 25.));
+    assert_eq!(harness.app().rows, vec![1, 0]);
+    harness.mouse_down((50., 75.), MouseButton::Right);
+    harness.mouse_up((50., 75.), MouseButton::Right);
+    assert_eq!(harness.app().rows, vec![1]);
+    assert_eq!(
+        harness.app().log,
+        vec![RowMsg::Mounted(0), RowMsg::Mounted(1), RowMsg::Unmounted(0)]
+    );
+}
+
+#[test]
+fn rows_sharing_a_key_are_reused() {
+    let mut harness = Harness::new(Rows::sharing_key(vec![1, 0]));
+    harness.layout((200., 200.));
+    harness.click((50., 25.));
+    assert_eq!(harness.app().rows, vec![2, 1, 0]);
+    // the old widgets went to the first two rows, only the last one is new,
+    // and none was dropped
+    assert_eq!(
+        harness.app().log,
+        vec![RowMsg::Mounted(1), RowMsg::Mounted(0), RowMsg::Mounted(0)]
+    );
+}
+
+/// Rows with their mount messages shifted by a [`Map`], and rows next to it.
+#[derive(Default)]
+struct MappedRows {
+    log: Vec<RowMsg>,
+}
+
+impl Application for MappedRows {
+    type Msg = RowMsg;
+
+    fn update(&mut self, msg: RowMsg) -> Command<RowMsg> {
+        self.log.push(msg);
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        let shift = |msg| match msg {
+            RowMsg::Mounted(row) => RowMsg::Mounted(row + 10),
+            msg => msg,
+        };
+        Column()
+            .child(Map(shift, Row(0)))
+            // the row emits no `u32`s, so nothing is mapped
+            .child(Map(|_: u32| RowMsg::Insert, Row(1)))
+            .child(Row(2))
+            .any()
+    }
+}
+
+#[test]
+fn map_only_maps_its_own_messages() {
+    let harness = Harness::new(MappedRows::default());
+    assert_eq!(
+        harness.app().log,
+        vec![RowMsg::Mounted(10), RowMsg::Mounted(1), RowMsg::Mounted(2)]
+    );
+}
+
+/// How often a [`Counted`] child was laid out and painted.
+#[derive(Default)]
+struct Counts {
+    layouts: Cell<usize>,
+    paints: Cell<usize>,
+}
+
+/// Counts how often its child is laid out and painted.
+struct Counted<'a>(Rc<Counts>, AnyView<'a>);
+
+struct CountedWidget(Rc<Counts>, AnyWidget);
+
+impl<'a> View<'a> for Counted<'a> {
+    type Widget = CountedWidget;
+
+    fn build(self) -> CountedWidget {
+        CountedWidget(self.0, self.1.build())
+    }
+
+    fn update(self, widget: &mut CountedWidget, ctx: &mut EventCtx) {
+        self.1.update(&mut widget.1, ctx);
+    }
+}
+
+impl Widget for CountedWidget {
+    fn as_ui_widget(&mut self) -> &mut dyn UiWidget {
+        self
+    }
+}
+
+impl UiWidget for CountedWidget {
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        self.1.mount(ctx);
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx, bc: BoxConstraints) -> Size {
+        self.0.layouts.set(self.0.layouts.get() + 1);
+        self.1.layout(ctx, bc)
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx) {
+        self.0.paints.set(self.0.paints.get() + 1);
+        self.1.paint(ctx);
+    }
+}
+
+/// An app showing a label that the tests change through its sink.
+trait Labelled: Application<Msg = String> {
+    fn sink(&self) -> &ExtEventSink<String>;
+}
+
+fn relabel<A: Labelled>(harness: &mut Harness<A>, label: &str) {
+    harness.app().sink().submit(label.to_string());
+    harness.run_ext_events();
+}
+
+#[derive(Default)]
+struct Relabel {
+    label: String,
+    sink: Option<ExtEventSink<String>>,
+    /// The column holding the label, the text next to the label, and the
+    /// text in the other column.
+    counts: [Rc<Counts>; 3],
+}
+
+impl Relabel {
+    fn layouts(&self) -> [usize; 3] {
+        [0, 1, 2].map(|i| self.counts[i].layouts.get())
+    }
+}
+
+impl Labelled for Relabel {
+    fn sink(&self) -> &ExtEventSink<String> {
+        self.sink.as_ref().unwrap()
+    }
+}
+
+impl Application for Relabel {
+    type Msg = String;
+
+    fn init(&mut self, sink: ExtEventSink<String>) {
+        self.sink = Some(sink);
+    }
+
+    fn update(&mut self, label: String) -> Command<String> {
+        self.label = label;
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        let [path, sibling, other] = self.counts.clone();
+        let column = Column()
+            .child(Text::new(self.label.as_str()))
+            .child(Counted(sibling, Text::new("fixed").any()));
+        Column()
+            .child(Counted(path, column.any()))
+            .child(Counted(other, Text::new("other").any()))
+            .any()
+    }
+}
+
+#[test]
+fn layout_skips_unchanged_subtrees() {
+    let mut harness = Harness::new(Relabel::default());
+    harness.layout((200., 200.));
+    let [path, sibling, other] = harness.app().layouts();
+    assert!(path > 0 && sibling > 0 && other > 0);
+
+    relabel(&mut harness, "");
+    assert_eq!(harness.app().layouts(), [path, sibling, other]);
+
+    // only the widgets between the root and the label are laid out again
+    relabel(&mut harness, "changed");
+    assert_eq!(harness.app().layouts(), [path + 1, sibling, other]);
+}
+
+/// A label in a [`RepaintBoundary`], above a plain label.
+#[derive(Default)]
+struct Panels {
+    label: String,
+    sink: Option<ExtEventSink<String>>,
+    /// The cached label and the plain one.
+    counts: [Rc<Counts>; 2],
+}
+
+impl Panels {
+    fn paints(&self) -> [usize; 2] {
+        [0, 1].map(|i| self.counts[i].paints.get())
+    }
+}
+
+impl Labelled for Panels {
+    fn sink(&self) -> &ExtEventSink<String> {
+        self.sink.as_ref().unwrap()
+    }
+}
+
+impl Application for Panels {
+    type Msg = String;
+
+    fn init(&mut self, sink: ExtEventSink<String>) {
+        self.sink = Some(sink);
+    }
+
+    fn update(&mut self, label: String) -> Command<String> {
+        self.label = label;
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        let [cached, plain] = self.counts.clone();
+        let label = Text::new(self.label.as_str()).fix_size(100., 50.);
+        Column()
+            .child(RepaintBoundary(Counted(cached, label.any())))
+            .child(Counted(plain, Text::new("plain").fix_size(100., 50.).any()))
+            .any()
+    }
+}
+
+#[test]
+fn paint_skips_widgets_outside_the_damage() {
+    let mut harness = Harness::new(Panels::default());
+    harness.layout((100., 100.));
+    harness.render_rect(Rect::new(0., 60., 100., 100.)).unwrap();
+    assert_eq!(harness.app().paints(), [0, 1]);
+    harness.render_rect(Rect::new(0., 0., 100., 40.)).unwrap();
+    assert_eq!(harness.app().paints(), [1, 1]);
+}
+
+#[test]
+fn repaint_boundary_reuses_its_image() {
+    let mut harness = Harness::new(Panels::default());
+    harness.layout((100., 100.));
+    harness.render().unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.app().paints(), [1, 2]);
+
+    // a new label requests paint, so the image is painted again
+    relabel(&mut harness, "changed");
+    harness.render().unwrap();
+    assert_eq!(harness.app().paints(), [2, 3]);
+    harness.render().unwrap();
+    assert_eq!(harness.app().paints(), [2, 4]);
+}
+
+#[test]
+fn scale_change_lays_out_and_paints_again() {
+    let mut harness = Harness::new(Relabel::default());
+    harness.layout((200., 200.));
+    let [path, sibling, other] = harness.app().layouts();
+    harness.set_scale(Scale::new(2., 2.));
+    assert_eq!(harness.app().layouts(), [path + 1, sibling + 1, other + 1]);
+    harness.set_scale(Scale::new(2., 2.));
+    assert_eq!(harness.app().layouts(), [path + 1, sibling + 1, other + 1]);
+
+    // the cached image is painted again at the new scale
+    let mut harness = Harness::new(Panels::default());
+    harness.layout((100., 100.));
+    harness.render().unwrap();
+    harness.set_scale(Scale::new(2., 2.));
+    harness.render().unwrap();
+    assert_eq!(harness.app().paints(), [2, 2]);
+}
+
+/// The counter's button with some space below it.
+struct Form {
+    count: i32,
+}
+
+impl Application for Form {
+    type Msg = Msg;
+
+    fn update(&mut self, msg: Msg) -> Command<Msg> {
+        match msg {
+            Msg::Increment => self.count += 1,
+        }
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        Column()
+            .child(Button::new("+").click(|| Msg::Increment))
+            .child(Text::new("").fix_size(200., 50.))
+            .any()
+    }
+}
+
+#[test]
+fn focus_survives_mouse_moves() {
+    let mut harness = Harness::new(Form { count: 0 });
+    harness.layout((200., 100.));
+    harness.key_down(key(KbKey::Tab));
+    harness.mouse_move((100., 90.));
+    assert!(harness.key_down(key(KbKey::Enter)));
+    assert_eq!(harness.app().count, 1);
+}
+
+#[test]
+fn leaving_window_sends_leave_without_moves() {
+    let mut harness = Harness::new(Probes::default());
+    harness.layout((200., 100.));
+    harness.mouse_move((50., 75.));
+    let moved = harness.app().moved.len();
+    harness.mouse_leave();
+    assert_eq!(harness.app().left, vec![1]);
+    assert_eq!(harness.app().moved.len(), moved);
+    let root = harness.inspect();
+    assert!(!root.hovered);
+    assert!(root.children.iter().all(|child| !child.hovered));
+}
+
+enum SwitchMsg {
+    Toggle,
+    Increment,
+}
+
+/// A child that is a button or a text, switched through the sink.
+struct Switch {
+    button: bool,
+    count: i32,
+    sink: Option<ExtEventSink<SwitchMsg>>,
+}
+
+impl Switch {
+    fn toggle(harness: &mut Harness<Switch>) {
+        harness.app().sink.as_ref().unwrap().submit(SwitchMsg::Toggle);
+        harness.run_ext_events();
+    }
+}
+
+impl Application for Switch {
+    type Msg = SwitchMsg;
+
+    fn init(&mut self, sink: ExtEventSink<SwitchMsg>) {
+        self.sink = Some(sink);
+    }
+
+    fn update(&mut self, msg: SwitchMsg) -> Command<SwitchMsg> {
+        match msg {
+            SwitchMsg::Toggle => self.button = !self.button,
+            SwitchMsg::Increment => self.count += 1,
+        }
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        // the same slot of the column, with a different widget type
+        let column = Column();
+        let column = if self.button {
+            column.child(Button::new("+").click(|| SwitchMsg::Increment))
+        } else {
+            column.child(Text::new("+"))
+        };
+        column.any()
+    }
+}
+
+#[test]
+fn switching_widget_type_resets_focus() {
+    let mut harness = Harness::new(Switch {
+        button: true,
+        count: 0,
+        sink: None,
+    });
+    harness.layout((200., 100.));
+    harness.key_down(key(KbKey::Tab));
+    assert!(harness.inspect().children[0].keyboard_focus);
+
+    // the text takes the button's place, but not its focus
+    Switch::toggle(&mut harness);
+    assert!(harness.inspect().children[0].type_name.ends_with("Text"));
+    assert!(!harness.inspect().children[0].keyboard_focus);
+    harness.key_down(key(KbKey::Tab));
+    assert!(!harness.inspect().children[0].keyboard_focus);
+    assert!(!harness.key_down(key(KbKey::Enter)));
+
+    Switch::toggle(&mut harness);
+    harness.key_down(key(KbKey::Tab));
+    assert!(harness.key_down(key(KbKey::Enter)));
+    assert_eq!(harness.app().count, 1);
+}
+
+#[derive(Debug, PartialEq)]
+enum CommandMsg {
+    Start,
+    Tick(u32),
+    Done(u32),
+}
+
+/// Runs `command` when its button is clicked, and logs everything else.
+struct Commands {
+    command: fn() -> Command<CommandMsg>,
+    log: Vec<CommandMsg>,
+}
+
+impl Commands {
+    fn new(command: fn() -> Command<CommandMsg>) -> Self {
+        Commands {
+            command,
+            log: Vec::new(),
+        }
+    }
+}
+
+impl Application for Commands {
+    type Msg = CommandMsg;
+
+    fn update(&mut self, msg: CommandMsg) -> Command<CommandMsg> {
+        match msg {
+            CommandMsg::Start => return (self.command)(),
+            msg => self.log.push(msg),
+        }
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        Button::new("start").click(|| CommandMsg::Start).any()
+    }
+}
+
+#[test]
+fn after_delivers_on_its_timer() {
+    let mut harness = Harness::new(Commands::new(|| {
+        Command::after(Duration::from_millis(10), CommandMsg::Tick(1))
+    }));
+    harness.layout((200., 100.));
+    harness.click((100., 50.));
+    let timers = harness.take_timers();
+    assert_eq!(timers.len(), 1);
+    assert_eq!(harness.app().log, vec![]);
+
+    harness.timer(timers[0]);
+    assert_eq!(harness.app().log, vec![CommandMsg::Tick(1)]);
+    // the timer is used up
+    harness.timer(timers[0]);
+    assert_eq!(harness.app().log, vec![CommandMsg::Tick(1)]);
+}
+
+#[test]
+fn batch_runs_every_command() {
+    let mut harness = Harness::new(Commands::new(|| {
+        Command::batch([
+            Command::after(Duration::from_millis(10), CommandMsg::Tick(1)),
+            Command::none(),
+            Command::after(Duration::from_millis(20), CommandMsg::Tick(2)),
+        ])
+    }));
+    harness.layout((200., 100.));
+    harness.click((100., 50.));
+    let timers = harness.take_timers();
+    assert_eq!(timers.len(), 2);
+
+    harness.timer(timers[1]);
+    harness.timer(timers[0]);
+    assert_eq!(
+        harness.app().log,
+        vec![CommandMsg::Tick(2), CommandMsg::Tick(1)]
+    );
+}
+
+#[test]
+fn perform_delivers_through_ext_events() {
+    let mut harness = Harness::new(Commands::new(|| {
+        Command::perform(async { 6 * 7 }, CommandMsg::Done)
+    }));
+    harness.layout((200., 100.));
+    harness.click((100., 50.));
+    // the future runs on its own thread
+    for _ in 0..100 {
+        harness.run_ext_events();
+        if !harness.app().log.is_empty() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(harness.app().log, vec![CommandMsg::Done(42)]);
+}
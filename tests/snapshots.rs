@@ -0,0 +1,65 @@
+//! Golden image tests, run with `OLMA_BLESS=1` to update the images.
+//!
+//! The tests without a golden image in `tests/snapshots` are ignored, bless
+//! them with `OLMA_BLESS=1 cargo test --test snapshots -- --ignored`.
+
+use olma::core::AnyView;
+use olma::piet::Color;
+use olma::widgets::*;
+use olma::{Application, Command, Harness, ViewExt};
+
+enum Scene {
+    RoundedBorder,
+    Button,
+}
+
+impl Application for Scene {
+    type Msg = ();
+
+    fn update(&mut self, _msg: ()) -> Command<()> {
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        match self {
+            Scene::RoundedBorder => Background::new(Text::new("").fix_size(60., 40.))
+                .background(Color::WHITE)
+                .border(Color::BLACK, 2.)
+                .rounded(8.)
+                .center()
+                .any(),
+            Scene::Button => Button::new("Ok").center().any(),
+        }
+    }
+}
+
+fn golden(name: &str) -> String {
+    format!(
+        "{}/tests/snapshots/{}.png",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+#[test]
+#[ignore = "golden image not generated yet, run with OLMA_BLESS=1"]
+fn rounded_border() {
+    let mut harness = Harness::new(Scene::RoundedBorder);
+    harness.layout((100., 100.));
+    harness
+        .render()
+        .unwrap()
+        .assert_golden(golden("rounded_border"), 2);
+}
+
+#[test]
+#[ignore = "golden image not generated yet, run with OLMA_BLESS=1"]
+fn button_hovered() {
+    let mut harness = Harness::new(Scene::Button);
+    harness.layout((100., 100.));
+    harness.mouse_move((50., 50.));
+    harness
+        .render()
+        .unwrap()
+        .assert_golden(golden("button_hovered"), 8);
+}
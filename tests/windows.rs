@@ -0,0 +1,160 @@
+use olma::core::{AnyView, View, Widget};
+use olma::widgets::*;
+use olma::{
+    Application, Command, EventCtx, ExtEventSink, Harness, UiWidget, ViewExt, WindowConfig,
+    WindowId, WindowView,
+};
+
+/// The content of a document window, that reports when it is mounted.
+struct Page(u32);
+
+struct PageWidget(u32);
+
+impl<'a> View<'a> for Page {
+    type Widget = PageWidget;
+
+    fn build(self) -> PageWidget {
+        PageWidget(self.0)
+    }
+
+    fn update(self, _widget: &mut PageWidget, _ctx: &mut EventCtx) {}
+}
+
+impl Widget for PageWidget {
+    fn as_ui_widget(&mut self) -> &mut dyn UiWidget {
+        self
+    }
+}
+
+impl UiWidget for PageWidget {
+    fn mount(&mut self, ctx: &mut EventCtx) {
+        ctx.submit_message(Box::new(DocsMsg::Mounted(self.0)));
+    }
+
+    fn unmount(&mut self, ctx: &mut EventCtx) {
+        ctx.submit_message(Box::new(DocsMsg::Unmounted(self.0)));
+    }
+}
+
+/// An app with a window per document, that confirms closing one only on the
+/// second request.
+struct Docs {
+    open: Vec<u32>,
+    asked: usize,
+    log: Vec<DocsMsg>,
+    sink: Option<ExtEventSink<DocsMsg>>,
+}
+
+#[derive(Debug, PartialEq)]
+enum DocsMsg {
+    Open(u32),
+    Close(u32),
+    CloseRequested(WindowId),
+    Mounted(u32),
+    Unmounted(u32),
+}
+
+impl Docs {
+    fn new(open: Vec<u32>) -> Self {
+        Docs {
+            open,
+            asked: 0,
+            log: Vec::new(),
+            sink: None,
+        }
+    }
+
+    fn send(harness: &mut Harness<Docs>, msg: DocsMsg) {
+        harness.app().sink.as_ref().unwrap().submit(msg);
+        harness.run_ext_events();
+    }
+}
+
+impl Application for Docs {
+    type Msg = DocsMsg;
+
+    fn init(&mut self, sink: ExtEventSink<DocsMsg>) {
+        self.sink = Some(sink);
+    }
+
+    fn update(&mut self, msg: DocsMsg) -> Command<DocsMsg> {
+        match msg {
+            DocsMsg::Open(doc) => self.open.push(doc),
+            DocsMsg::Close(doc) => self.open.retain(|&d| d != doc),
+            DocsMsg::CloseRequested(id) => {
+                self.asked += 1;
+                if self.asked > 1 {
+                    return Command::close_window(id);
+                }
+            }
+            msg => self.log.push(msg),
+        }
+        Command::none()
+    }
+
+    fn view<'a>(&'a self) -> AnyView<'a> {
+        Text::new("main").any()
+    }
+
+    fn windows<'a>(&'a self) -> Vec<WindowView<'a>> {
+        self.open
+            .iter()
+            .map(|&doc| WindowView::new(doc, WindowConfig::default(), Page(doc).any()))
+            .collect()
+    }
+
+    fn close_requested(&self, window: WindowId) -> Option<DocsMsg> {
+        Some(DocsMsg::CloseRequested(window))
+    }
+}
+
+fn doc(key: u32) -> WindowId {
+    WindowId::Keyed(Key::new(key))
+}
+
+#[test]
+fn keyed_windows_follow_the_app() {
+    let mut harness = Harness::new(Docs::new(vec![1]));
+    harness.layout((200., 100.));
+    assert_eq!(harness.windows(), vec![WindowId::Main, doc(1)]);
+    assert_eq!(harness.app().log, vec![DocsMsg::Mounted(1)]);
+
+    // the open window is updated, not opened again
+    Docs::send(&mut harness, DocsMsg::Open(2));
+    assert_eq!(harness.windows(), vec![WindowId::Main, doc(1), doc(2)]);
+    assert_eq!(
+        harness.app().log,
+        vec![DocsMsg::Mounted(1), DocsMsg::Mounted(2)]
+    );
+
+    Docs::send(&mut harness, DocsMsg::Close(1));
+    assert_eq!(harness.windows(), vec![WindowId::Main, doc(2)]);
+    assert_eq!(
+        harness.app().log,
+        vec![DocsMsg::Mounted(1), DocsMsg::Mounted(2), DocsMsg::Unmounted(1)]
+    );
+}
+
+#[test]
+fn close_can_be_vetoed() {
+    let mut harness = Harness::new(Docs::new(vec![1]));
+    harness.layout((200., 100.));
+    assert_eq!(harness.windows(), vec![WindowId::Main, doc(1)]);
+
+    harness.request_close(doc(1));
+    assert_eq!(harness.app().asked, 1);
+    assert_eq!(harness.windows(), vec![WindowId::Main, doc(1)]);
+    assert_eq!(harness.app().log, vec![DocsMsg::Mounted(1)]);
+
+    harness.request_close(doc(1));
+    assert_eq!(harness.app().asked, 2);
+    assert_eq!(harness.windows(), vec![WindowId::Main]);
+    assert_eq!(
+        harness.app().log,
+        vec![DocsMsg::Mounted(1), DocsMsg::Unmounted(1)]
+    );
+
+    // the key is still listed, but the window stays closed
+    Docs::send(&mut harness, DocsMsg::Open(2));
+    assert_eq!(harness.windows(), vec![WindowId::Main, doc(2)]);
+}